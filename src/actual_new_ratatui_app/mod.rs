@@ -14,7 +14,7 @@
 //! [examples readme]: https://github.com/ratatui/ratatui/blob/main/examples/README.md
 
 use std::collections::hash_map::Keys;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::env::remove_var;
 use std::net::ToSocketAddrs;
 use std::ops::Index;
@@ -25,7 +25,7 @@ use std::time::Duration;
 use color_eyre::owo_colors::OwoColorize;
 use color_eyre::Result;
 use dirs::config_dir;
-use ratatui::{buffer::Buffer, crossterm::event::{self, Event, KeyCode, KeyEventKind}, layout::{Constraint, Layout, Rect}, style::{palette::tailwind, Color, Stylize}, symbols, text, text::{Line, Text}, widgets::{Block, Padding, Paragraph, Tabs, Widget}, DefaultTerminal};
+use ratatui::{buffer::Buffer, crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind}, layout::{Constraint, Layout, Margin, Position, Rect}, style::{palette::tailwind, Color, Modifier, Stylize}, symbols, text::{Line, Span, Text}, widgets::{Block, Padding, Paragraph, Tabs, Widget}, DefaultTerminal};
 use serde::{Deserialize, Serialize};
 
 use ratatui::{
@@ -34,14 +34,18 @@ use ratatui::{
     Frame,
 };
 use ratatui::prelude::StatefulWidget;
-use ratatui::style::palette::material::{RED, YELLOW};
-use ratatui::style::palette::tailwind::{GREEN, SLATE, STONE};
 use ratatui::widgets::{HighlightSpacing, ListItem, Wrap};
 use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
-use crate::functions::print_different_versions;
+use crate::functions::{get_detected_steam_libraries, print_different_versions, validate_xrd_game_folder, SteamLibrary};
 use crate::manager::Manager;
+use crate::error::{XrdError, XrdResult};
 use crate::stuff;
-use crate::stuff::{AppStruct, TagInfo};
+use crate::stuff::{AppStruct, AppType, PatchOutput, TagInfo};
+use crate::cache::CachedRelease;
+use crate::markdown::markdown_to_lines;
+use crate::version::VersionComparison;
+use crate::keybindings::{Action, Keymap};
+use crate::theme::{get_built_in_theme_names, get_theme, is_no_color_set, set_theme, Theme, DEFAULT_THEME_NAME};
 
 use derive_setters::Setters;
 use itertools::Itertools;
@@ -65,8 +69,8 @@ use tokio::task;
 enum SubMenus {
     #[default]
     None,
-    UpdateSingleApps,
-    UpdateAllApps,
+    // Apps confirmed to be updated
+    UpdateApps(Vec<String>),
     UpdateAllCompleted
 }
 
@@ -83,7 +87,10 @@ enum AppUpdatingStatusStatus {
     Pending,
     OnGoing,
     Updated,
-    Failed
+    Failed,
+    // Skipped
+    NotChecked,
+    UpToDate
 }
 
 struct AppUpdatingStatus {
@@ -99,14 +106,18 @@ impl AppUpdatingStatus {
                     AppUpdatingStatusStatus::OnGoing => {"On Going"}
                     AppUpdatingStatusStatus::Updated => {"Updated"}
                     AppUpdatingStatusStatus::Failed  => {"Failed"}
+                    AppUpdatingStatusStatus::NotChecked => {"Skipped, search updates first"}
+                    AppUpdatingStatusStatus::UpToDate => {"Skipped, no new version found"}
                 }).to_string()
     }
     fn get_status_render_colour(&self) -> Color {
+        let theme = get_theme();
         match self.status {
-            AppUpdatingStatusStatus::Pending => GREY_TEXT_FG_COLOR,
-            AppUpdatingStatusStatus::OnGoing => YELLOW_TEXT_FG_COLOR,
-            AppUpdatingStatusStatus::Updated => COMPLETED_TEXT_FG_COLOR,
-            AppUpdatingStatusStatus::Failed  => RED_TEXT_FG_COLOR
+            AppUpdatingStatusStatus::Pending => theme.muted,
+            AppUpdatingStatusStatus::OnGoing => theme.warning,
+            AppUpdatingStatusStatus::Updated => theme.success,
+            AppUpdatingStatusStatus::Failed  => theme.error,
+            AppUpdatingStatusStatus::NotChecked | AppUpdatingStatusStatus::UpToDate => theme.muted
         }
     }
 
@@ -115,13 +126,8 @@ impl AppUpdatingStatus {
 
 
 // Consts
-const NORMAL_ROW_BG: Color = SLATE.c950;
-const ALT_ROW_BG_COLOR: Color = SLATE.c900;
-const TEXT_FG_COLOR: Color = SLATE.c200;
-const COMPLETED_TEXT_FG_COLOR: Color = GREEN.c500;
-const GREY_TEXT_FG_COLOR: Color = SLATE.c300;
-const YELLOW_TEXT_FG_COLOR: Color = YELLOW.c200 ;
-const RED_TEXT_FG_COLOR: Color = RED.a700 ;
+// Lines scrolled with PageUp/PageDown
+const RELEASE_NOTES_SCROLL_STEP: u16 = 5;
 
 #[derive(Default)]
 pub struct App {
@@ -131,13 +137,99 @@ pub struct App {
     // config_manager: Manager, // Tabs shouldn't use this. Used to populate tabs/pivot point.
     app_struct_list_menu: AppStructListMenu,
     active_tab_storage: TabStorage,
-    latest_pulled_tags_hashmap: HashMap<String,TagInfo>,
+    latest_pulled_tags_hashmap: HashMap<String,CachedRelease>,
+    // Releases between the installed and the latest one, newest first.
+    pulled_changelogs_hashmap: HashMap<String,Vec<TagInfo>>,
+    // Lines scrolled in the release notes of the selected mod.
+    release_notes_scroll: u16,
+    patch_outputs_hashmap: HashMap<String, PatchOutput>,
     update_apps_status_hashmap: HashMap<String, AppUpdatingStatus>,
     lock_inputs: bool,
+    // Error to display on top of everything else, until dismissed.
+    error_popup: Option<String>,
+    // Game folder being typed in the settings tab.
+    game_folder_input: Option<String>,
+    clickable_areas: ClickableAreas,
+    // Loaded from the settings on every reload.
+    keymap: Keymap,
+    // Keys of the current tab, until any key is pressed.
+    show_help: bool,
+    // Mods enabled/disabled since the config was loaded or saved.
+    unsaved_changes: bool,
+    // Asks before changing the mods or the game files, and before throwing the unsaved changes away.
+    dialog: Option<Dialog>,
     n: i32,
     stored_thread: Option<JoinHandle<()>>
 }
 
+// Areas drawn on the last render, to find what was clicked.
+#[derive(Default)]
+struct ClickableAreas {
+    tabs: Vec<(Rect, SelectedTab)>,
+    // Rows of the list of the current tab, without borders.
+    list: Rect,
+    release_notes: Rect,
+    popup_close_button: Option<Rect>,
+    dialog_buttons: Vec<Rect>,
+}
+
+// Actions that reload the config from disk, throwing the unsaved changes away.
+#[derive(Clone, Copy)]
+enum DiscardableAction {
+    Quit,
+    SelectTab(SelectedTab),
+    Reload,
+}
+
+// Run once its dialog button is picked.
+#[derive(Clone)]
+enum DialogAction {
+    Cancel,
+    UpdateApps(Vec<String>),
+    ReinstallApp(String),
+    UninstallApps(Vec<String>),
+    PatchApps(Vec<String>),
+    SwitchProfile(String),
+    RestoreGameFiles,
+    SaveAndThen(DiscardableAction),
+    DiscardAndThen(DiscardableAction),
+}
+
+struct DialogButton {
+    label: &'static str,
+    // Picks the button right away.
+    key: char,
+    action: DialogAction,
+}
+
+// Modal with a summary of what is about to change.
+struct Dialog {
+    title: String,
+    summary: Vec<String>,
+    buttons: Vec<DialogButton>,
+    // Picked with Enter
+    selected_button: usize,
+}
+
+impl Dialog {
+    // Yes/No, No being the default.
+    fn confirm(title: String, summary: Vec<String>, action: DialogAction) -> Dialog {
+        Dialog {
+            title,
+            summary,
+            buttons: vec![
+                DialogButton { label: "Yes (y)", key: 'y', action },
+                DialogButton { label: "No (n/Esc)", key: 'n', action: DialogAction::Cancel },
+            ],
+            selected_button: 1,
+        }
+    }
+
+    fn get_cancel_button(&self) -> Option<usize> {
+        self.buttons.iter().position(|button| matches!(button.action, DialogAction::Cancel))
+    }
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 enum AppState {
     #[default]
@@ -145,7 +237,7 @@ enum AppState {
     Quitting,
 }
 
-#[derive(Default, Clone, Copy, Display, FromRepr, EnumIter)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Display, FromRepr, EnumIter)]
 enum SelectedTab {
     #[default]
     #[strum(to_string = "Select Visible Mods")]
@@ -156,35 +248,51 @@ enum SelectedTab {
     Tab3,
     #[strum(to_string = "Launch Mods")]
     Tab4,
+    #[strum(to_string = "Settings")]
+    Tab5,
 }
 
 impl SelectedTab {
-    pub(crate) fn describe_selected_mod_tag_description(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, latest_tags_pulled_map: &mut HashMap<String,TagInfo>) {
+    pub(crate) fn describe_selected_mod_tag_description(self, area: Rect, buffer: &mut Buffer, selected_app: Option<&AppStruct>, latest_tags_pulled_map: &mut HashMap<String,CachedRelease>, changelogs_map: &HashMap<String,Vec<TagInfo>>, release_notes_scroll: &mut u16) {
+        let theme = get_theme();
 
-        let create_block = |title: String| Block::bordered().gray().title(title.bold());
+        let create_block = |title: String| Block::bordered().fg(theme.border).title(title.bold());
         let mut text_lines: Vec<Line>= vec![];
         let mut paragraph: Paragraph;
         let mut text: Text;
 
-        match tab_storage.list_state.selected() {
-            Some(index) => {
-                let app_name_list = tab_storage.get_enabled_app_names(); // self.config_manager.get_sorted_apps_name();
-                let app_name = app_name_list.get(index).unwrap().to_string();
-                let app = tab_storage.config_manager.config.apps.get(&app_name).unwrap();
+        match selected_app {
+            Some(app) => {
+                let app_name = app.get_app_name();
                 match latest_tags_pulled_map.get(&app.get_app_name()) {
                     None => {
-                        text_lines.push(Line::styled("No version found. Search for updates.".to_string(), YELLOW_TEXT_FG_COLOR));
+                        text_lines.push(Line::styled("No version found. Search for updates.".to_string(), theme.warning));
                         text = Text::from(text_lines);
-                        paragraph = Paragraph::new(text).gray().block(create_block(format!("{} '{}' -> '??'", app.get_app_name(), app.tag_name))).wrap(Wrap { trim: true });
+                        paragraph = Paragraph::new(text).fg(theme.text).block(create_block(format!("{} '{}' -> '??'", app.get_app_name(), app.tag_name))).wrap(Wrap { trim: true });
                     }
 
-                    Some(tag) => {
+                    Some(cached_release) => {
+                        let tag = &cached_release.tag_info;
                         // for line in tag.get_formated_body().to_string().split("\n") {
-                        //     text_lines.push(Line::styled(format!("{}", line.to_string()), COMPLETED_TEXT_FG_COLOR));
+                        //     text_lines.push(Line::styled(format!("{}", line.to_string()), theme.success));
                         // }
-                        // text = Text::from(tag.get_formated_body()).style(COMPLETED_TEXT_FG_COLOR);
-                        text = Text::from(tag.get_formated_body().to_string()).style(COMPLETED_TEXT_FG_COLOR);
-                        paragraph = Paragraph::new(text).gray().block(create_block(format!("{} '{}' -> '{}'", app.get_app_name(), app.tag_name, tag.tag_name.to_string()))).wrap(Wrap { trim: false });
+                        // text = Text::from(tag.get_formated_body()).style(theme.success);
+                        let changelog = changelogs_map.get(&app_name).cloned().unwrap_or_else(|| vec![tag.clone()]);
+                        let mut release_notes_lines: Vec<Line> = vec![];
+                        for release in &changelog {
+                            if !release_notes_lines.is_empty() {
+                                release_notes_lines.push(Line::default());
+                            }
+                            let prerelease_label = if release.prerelease { " (pre-release)" } else { "" };
+                            let published_date = release.published_at.split('T').next().unwrap_or_default();
+                            release_notes_lines.push(Line::styled(format!("── {} ({}){} ──", release.tag_name, published_date, prerelease_label), theme.warning).bold());
+                            release_notes_lines.extend(markdown_to_lines(&release.get_formated_body()));
+                        }
+                        // Keeps the notes visible when scrolled past the end.
+                        *release_notes_scroll = (*release_notes_scroll).min(release_notes_lines.len().saturating_sub(1) as u16);
+                        text = Text::from(release_notes_lines).style(theme.success);
+                        paragraph = Paragraph::new(text).fg(theme.text).block(create_block(format!("{} '{}' -> '{}' ({} release{}, checked {})", app.get_app_name(), app.tag_name, tag.tag_name, changelog.len(), if changelog.len() == 1 { "" } else { "s" }, cached_release.get_formated_age()))).wrap(Wrap { trim: false })
+                            .scroll((*release_notes_scroll, 0));
 
                         // println!("{}", tag.get_formated_body());
                         // println!("{:?}", tag.get_formated_body());
@@ -192,17 +300,73 @@ impl SelectedTab {
                         // println!("{:?}", tag.get_formated_body());
 
                         // sleep_ms(100000000);
-                        // description_line = Line::styled(format!("{} {}", tag.body, tag.body), COMPLETED_TEXT_FG_COLOR);
+                        // description_line = Line::styled(format!("{} {}", tag.body, tag.body), theme.success);
                     }
                 }
             }
             _ => {
-                text_lines.push(Line::styled("No mod selected.".to_string(), COMPLETED_TEXT_FG_COLOR));
+                text_lines.push(Line::styled("No mod selected.".to_string(), theme.success));
                 text = Text::from(text_lines);
-                paragraph = Paragraph::new(text).gray().block(create_block("".to_string()));
+                paragraph = Paragraph::new(text).fg(theme.text).block(create_block("".to_string()));
             }
         }
-        Clear.render(area, buffer);
+        clear_area(area, buffer);
+        Widget::render(paragraph, area, buffer);
+    }
+
+    pub(crate) fn describe_selected_mod_patch_output(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, patch_outputs_map: &HashMap<String, PatchOutput>) {
+        let theme = get_theme();
+
+        let create_block = |title: String| Block::bordered().fg(theme.border).title(title.bold());
+        let mut text_lines: Vec<Line>= vec![];
+
+        let paragraph: Paragraph = match tab_storage.list_state.selected() {
+            Some(index) => {
+                let app_name_list = tab_storage.get_enabled_app_names();
+                let app_name = app_name_list.get(index).unwrap().to_string();
+                let app = tab_storage.config_manager.config.apps.get(&app_name).unwrap();
+                match patch_outputs_map.get(&app.get_app_name()) {
+                    None => {
+                        match app.is_patchable() {
+                            true => text_lines.push(Line::styled("No patch executed during this session. Press p to patch.".to_string(), theme.warning)),
+                            false => text_lines.push(Line::styled("This mod doesn't have a patch procedure.".to_string(), theme.muted)),
+                        }
+                        Paragraph::new(Text::from(text_lines)).fg(theme.text).block(create_block(app.get_app_name())).wrap(Wrap { trim: true })
+                    }
+                    Some(patch_output) => {
+                        let (status_line, status_colour) = match patch_output.is_successful() {
+                            true => ("✓ Patch succeeded", theme.success),
+                            false => ("✗ Patch failed", theme.error)
+                        };
+                        text_lines.push(Line::styled(status_line.to_string(), status_colour).bold());
+                        for line in patch_output.get_exit_code_explanation().lines() {
+                            text_lines.push(Line::styled(line.to_string(), status_colour));
+                        }
+                        for copy_error in &patch_output.copy_errors {
+                            text_lines.push(Line::styled(copy_error.to_string(), theme.error));
+                        }
+                        if let Some(backup_warning) = &patch_output.backup_warning {
+                            text_lines.push(Line::styled(backup_warning.to_string(), theme.warning));
+                        }
+                        text_lines.push(Line::styled(format!("Log stored at '{}'", patch_output.log_file_path), theme.muted));
+                        text_lines.push(Line::styled("=== Stdout ===".to_string(), theme.muted).bold());
+                        for line in patch_output.stdout.replace("\r", "").lines() {
+                            text_lines.push(Line::styled(line.to_string(), theme.text));
+                        }
+                        text_lines.push(Line::styled("=== Stderr ===".to_string(), theme.muted).bold());
+                        for line in patch_output.stderr.replace("\r", "").lines() {
+                            text_lines.push(Line::styled(line.to_string(), theme.error));
+                        }
+                        Paragraph::new(Text::from(text_lines)).fg(theme.text).block(create_block(format!("{} ({})", app.get_app_name(), patch_output.date))).wrap(Wrap { trim: false })
+                    }
+                }
+            }
+            _ => {
+                text_lines.push(Line::styled("No mod selected.".to_string(), theme.success));
+                Paragraph::new(Text::from(text_lines)).fg(theme.text).block(create_block("".to_string()))
+            }
+        };
+        clear_area(area, buffer);
         Widget::render(paragraph, area, buffer);
     }
 }
@@ -211,9 +375,71 @@ impl SelectedTab {
 #[derive(Default)]
 struct TabStorage {
     config_manager: Manager,
+    steam_libraries: Vec<SteamLibrary>,
     // ordered_app_name_vector: Vec<String>,
     // ordered_app_vector: Vec<AppStruct>,
-    list_state: ListState
+    list_state: ListState,
+    // Apps marked with space, the batch actions apply to them instead of the highlighted one.
+    marked_app_names: BTreeSet<String>,
+    list_filter: ListFilter
+}
+
+// Search ('/') and filters of the mod lists.
+#[derive(Default)]
+struct ListFilter {
+    search: String,
+    // The search is being typed.
+    searching: bool,
+    only_outdated: bool,
+    only_patched: bool,
+    app_type: Option<AppType>,
+    repo_owner: Option<String>,
+}
+
+impl ListFilter {
+    fn is_active(&self) -> bool {
+        self.searching || !self.search.is_empty() || self.only_outdated || self.only_patched || self.app_type.is_some() || self.repo_owner.is_some()
+    }
+
+    fn matches(&self, app: &AppStruct, latest_release: Option<&CachedRelease>) -> bool {
+        fuzzy_match(&self.search, &app.get_app_name())
+            && (!self.only_outdated || latest_release.is_some_and(|latest_release| app.has_new_version(&latest_release.tag_info)))
+            && (!self.only_patched || app.patched)
+            && self.app_type.as_ref().is_none_or(|app_type| *app_type == app.app_type)
+            && self.repo_owner.as_ref().is_none_or(|repo_owner| *repo_owner == app.repo_owner)
+    }
+
+    fn get_description(&self) -> String {
+        let mut filters: Vec<String> = vec![];
+        if self.only_outdated {
+            filters.push("outdated".to_string());
+        }
+        if self.only_patched {
+            filters.push("patched".to_string());
+        }
+        if let Some(app_type) = &self.app_type {
+            filters.push(format!("type: {app_type}"));
+        }
+        if let Some(repo_owner) = &self.repo_owner {
+            filters.push(format!("owner: {repo_owner}"));
+        }
+        let cursor = if self.searching { "_" } else { "" };
+        format!("Search: {}{cursor} | Filters: {}", self.search, if filters.is_empty() { "none".to_string() } else { filters.join(", ") })
+    }
+}
+
+// Case-insensitive, the pattern characters must appear in order, e.g. "khit" matches "kkots/ggxrd_hitbox_overlay_2211".
+fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut text_chars = text.chars().flat_map(char::to_lowercase);
+    pattern.chars().flat_map(char::to_lowercase).all(|pattern_char| text_chars.any(|text_char| text_char == pattern_char))
+}
+
+// None -> first option -> ... -> last option -> None
+fn get_next_option<T: Clone + PartialEq>(options: &[T], current: &Option<T>) -> Option<T> {
+    match current {
+        None => options.first().cloned(),
+        Some(current) => options.iter().position(|option| option == current).and_then(|index| options.get(index + 1)).cloned(),
+    }
 }
 
 impl TabStorage {
@@ -233,6 +459,43 @@ impl TabStorage {
         // self.get_enabled_apps_name.to_owned()
 
     }
+
+    fn toggle_mark(&mut self, app_names: &[String]) {
+        if let Some(app_name) = self.list_state.selected().and_then(|index| app_names.get(index))
+            && !self.marked_app_names.remove(app_name) {
+            self.marked_app_names.insert(app_name.to_string());
+        }
+    }
+
+    // Unmarks all of them if they were already marked.
+    fn toggle_mark_all(&mut self, app_names: &[String]) {
+        match app_names.iter().all(|app_name| self.marked_app_names.contains(app_name)) {
+            true => self.marked_app_names.clear(),
+            false => self.marked_app_names.extend(app_names.iter().cloned()),
+        }
+    }
+
+    // Marked apps, or the highlighted one if none is marked.
+    fn get_target_app_names(&self, app_names: &[String]) -> Vec<String> {
+        let marked_app_names: Vec<String> = app_names.iter().filter(|app_name| self.marked_app_names.contains(*app_name)).cloned().collect();
+        if !marked_app_names.is_empty() {
+            return marked_app_names;
+        }
+        self.list_state.selected().and_then(|index| app_names.get(index)).cloned().into_iter().collect()
+    }
+
+    fn get_filtered_app_names(&self, app_names: Vec<String>, latest_tags_pulled_map: &HashMap<String,CachedRelease>) -> Vec<String> {
+        app_names.into_iter()
+            .filter(|app_name| self.config_manager.config.apps.get(app_name).is_some_and(|app| self.list_filter.matches(app, latest_tags_pulled_map.get(app_name))))
+            .collect()
+    }
+
+    fn get_mark(&self, app_name: &String) -> &'static str {
+        match self.marked_app_names.contains(app_name) {
+            true => "[x]",
+            false => "[ ]",
+        }
+    }
 }
 
 
@@ -268,67 +531,59 @@ impl App {
     // fn handle_events(&mut self) -> std::io::Result<()> {
     // fn handle_events(&mut self) -> std::io::Result<()> {
     fn handle_events(&mut self) -> std::io::Result<()> {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
+        let event = event::read()?;
+        if let Event::Mouse(mouse_event) = event {
+            self.handle_mouse_event(mouse_event);
+        }
+        if let Event::Key(key) = event {
+            if key.kind == KeyEventKind::Press && self.error_popup.is_some() {
+                // Inputs are locked until the error is dismissed.
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => { self.error_popup = None }
+                    _ => {}
+                }
+            }
+            else if key.kind == KeyEventKind::Press && self.show_help {
+                self.show_help = false;
+            }
+            else if key.kind == KeyEventKind::Press && let Some(dialog) = self.dialog.as_mut() {
+                let picked_button = match key.code {
+                    KeyCode::Left | KeyCode::BackTab => { dialog.selected_button = dialog.selected_button.saturating_sub(1); None }
+                    KeyCode::Right | KeyCode::Tab => { dialog.selected_button = (dialog.selected_button + 1).min(dialog.buttons.len() - 1); None }
+                    KeyCode::Enter => Some(dialog.selected_button),
+                    KeyCode::Esc => dialog.get_cancel_button(),
+                    KeyCode::Char(c) => dialog.buttons.iter().position(|button| button.key == c.to_ascii_lowercase()),
+                    _ => None,
+                };
+                if let Some(button_index) = picked_button {
+                    self.pick_dialog_button(button_index);
+                }
+            }
+            else if key.kind == KeyEventKind::Press {
                 match self.selected_tab {
-                    SelectedTab::Tab1 => {
+                    SelectedTab::Tab1 | SelectedTab::Tab2 if self.active_tab_storage.list_filter.searching => {
                         match key.code {
-                            // Tab specific
-                            KeyCode::Char('s') | KeyCode::Char('S') => { self.save_config() }
-                            KeyCode::Char('r') | KeyCode::Char('R')=> { self.reload_config() }
-
-                            // Movement
-                            KeyCode::Enter => { self.toggle_enable_disable_mod()}
+                            KeyCode::Enter => { self.active_tab_storage.list_filter.searching = false }
+                            KeyCode::Esc => { self.edit_list_filter(|list_filter| { list_filter.search.clear(); list_filter.searching = false }) }
+                            KeyCode::Backspace => { self.edit_list_filter(|list_filter| { list_filter.search.pop(); }) }
+                            KeyCode::Char(c) => { self.edit_list_filter(|list_filter| list_filter.search.push(c)) }
                             KeyCode::Up => { self.select_previous() }
                             KeyCode::Down => { self.select_next() }
-
-                            // Tab Movement
-                            KeyCode::Right => self.next_tab(),
-                            KeyCode::Left => self.previous_tab(),
-
-                            // Others
-                            KeyCode::Char('q') | KeyCode::Char('Q')| KeyCode::Esc => self.quit(),
                             _ => {}
                         }
                     }
-                    SelectedTab::Tab2 => {
+                    SelectedTab::Tab5 if self.game_folder_input.is_some() => {
                         match key.code {
-                            // Tab specific
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { self.download_patches() }
-                            KeyCode::Char('s') | KeyCode::Char('S')=> { self.pull_latest_tags() } // Only find the latest for each app
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { terminal.draw(update_app)?;     sleep_ms(10000000); } // Only find the latest for each app
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { self.test_popup(terminal) } // Only find the latest for each app
-                            KeyCode::Char('u') | KeyCode::Char('U')=> { self.update_all_enabled_mods() } // Only find the latest for each app
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { self.update_all_enabled_mods(terminal) } // Only find the latest for each app
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { terminal.draw(self.update_all_enabled_mods)?; sleep_ms(100000); } // Only find the latest for each app
-                            // KeyCode::Char('u') | KeyCode::Char('U')=> { self.update_all_enabled_mods(terminal) } // Only find the latest for each app
-                            // KeyCode::Char('p') | KeyCode::Char('P') => { self.patch() }
-
-                            // Movement
-                            // KeyCode::Enter => { self.toggle_enable_disable_mod()} // Update single one (or in the future open a new window to select the desired patch :shrug:)
-                            KeyCode::Up => { self.select_previous() }
-                            KeyCode::Down => { self.select_next() }
-
-                            // Tab Movement
-                            KeyCode::Right => self.next_tab(),
-                            KeyCode::Left => self.previous_tab(),
-
-                            // Others
-                            KeyCode::Char('q') | KeyCode::Char('Q')| KeyCode::Esc => self.quit(),
+                            KeyCode::Enter => { self.set_game_folder_from_input() }
+                            KeyCode::Esc => { self.game_folder_input = None }
+                            KeyCode::Backspace => { if let Some(input) = self.game_folder_input.as_mut() { input.pop(); } }
+                            KeyCode::Char(c) => { if let Some(input) = self.game_folder_input.as_mut() { input.push(c); } }
                             _ => {}
                         }
                     }
-                    // SelectedTab::Tab3 => {}
-                    // SelectedTab::Tab4 => {}
                     _ => {
-                        match key.code {
-                            // Tab Movement
-                            KeyCode::Right => self.next_tab(),
-                            KeyCode::Left => self.previous_tab(),
-
-                            // Others
-                            KeyCode::Char('q') | KeyCode::Char('Q')| KeyCode::Esc => self.quit(),
-                            _ => {}
+                        if let Some(action) = self.keymap.get_action(self.selected_tab.get_actions(), key.code) {
+                            self.run_action(action);
                         }
                     }
                 }
@@ -337,26 +592,147 @@ impl App {
         Ok(())
     }
 
-    fn select_next(&mut self) {
-        self.active_tab_storage.list_state.select_next();
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.quit(),
+            Action::NextTab => self.next_tab(),
+            Action::PreviousTab => self.previous_tab(),
+            Action::SelectNext => self.select_next(),
+            Action::SelectPrevious => self.select_previous(),
+            Action::SelectFirst => self.select_first(),
+            Action::SelectLast => self.select_last(),
+            Action::Help => self.show_help = true,
+            Action::Save => self.save_config(),
+            Action::Reload => {
+                match self.selected_tab {
+                    // Detects the Steam libraries again too
+                    SelectedTab::Tab5 => self.reset_active_tab_storage(),
+                    _ => {
+                        if !self.ask_before_discarding(DiscardableAction::Reload) {
+                            self.reload_config();
+                        }
+                    }
+                }
+            }
+
+            // Tab 1/Tab 2
+            Action::ToggleEnabled => self.toggle_enable_disable_mod(),
+            Action::Mark => self.mark_selected_mod(),
+            Action::MarkAll => self.mark_all_mods(),
+            Action::Search => self.edit_list_filter(|list_filter| list_filter.searching = true),
+            Action::FilterOutdated => self.edit_list_filter(|list_filter| list_filter.only_outdated ^= true),
+            Action::FilterPatched => self.edit_list_filter(|list_filter| list_filter.only_patched ^= true),
+            Action::FilterType => self.cycle_app_type_filter(),
+            Action::FilterOwner => self.cycle_repo_owner_filter(),
+            Action::ClearFilters => self.edit_list_filter(|list_filter| *list_filter = ListFilter::default()),
+
+            // Tab 2
+            Action::SearchUpdates => self.pull_latest_tags(),
+            Action::UpdateSelected => self.update_selected_mod(),
+            Action::UpdateAll => self.update_all_enabled_mods(),
+            Action::Reinstall => self.reinstall_selected_mod(),
+            Action::Uninstall => self.uninstall_selected_mods(),
+            Action::ScrollNotesDown => self.release_notes_scroll = self.release_notes_scroll.saturating_add(RELEASE_NOTES_SCROLL_STEP),
+            Action::ScrollNotesUp => self.release_notes_scroll = self.release_notes_scroll.saturating_sub(RELEASE_NOTES_SCROLL_STEP),
+
+            // Tab 3
+            Action::Patch => self.patch_selected_mods(),
+            Action::RestoreGameFiles => self.confirm_restore_game_files(),
+
+            // Tab 5
+            Action::UseSelectedLibrary => self.set_game_folder_from_selected_library(),
+            Action::EditGameFolder => self.game_folder_input = Some(self.active_tab_storage.config_manager.config.xrd_game_folder.to_string()),
+            Action::DetectGameFolder => self.set_game_folder(String::new()),
+            Action::SwitchProfile => self.switch_to_next_profile(),
+            Action::ToggleOfflineMode => self.toggle_offline_mode(),
+            Action::SwitchTheme => self.switch_to_next_theme(),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Inputs are locked until the error is dismissed.
+                if self.error_popup.is_some() {
+                    if self.clickable_areas.popup_close_button.is_some_and(|button_area| button_area.contains(position)) {
+                        self.error_popup = None;
+                    }
+                    return;
+                }
+                if self.show_help {
+                    self.show_help = false;
+                    return;
+                }
+                if self.dialog.is_some() {
+                    if let Some(button_index) = self.clickable_areas.dialog_buttons.iter().position(|button_area| button_area.contains(position)) {
+                        self.pick_dialog_button(button_index);
+                    }
+                    return;
+                }
+
+                if let Some((_, tab)) = self.clickable_areas.tabs.iter().find(|(tab_area, _)| tab_area.contains(position)) {
+                    self.select_tab(*tab);
+                }
+                else if self.clickable_areas.list.contains(position) {
+                    self.click_list_row(position);
+                }
+            }
+            MouseEventKind::ScrollDown if self.clickable_areas.release_notes.contains(position) => { self.release_notes_scroll = self.release_notes_scroll.saturating_add(1) }
+            MouseEventKind::ScrollUp if self.clickable_areas.release_notes.contains(position) => { self.release_notes_scroll = self.release_notes_scroll.saturating_sub(1) }
+            MouseEventKind::ScrollDown if self.clickable_areas.list.contains(position) => { self.select_next() }
+            MouseEventKind::ScrollUp if self.clickable_areas.list.contains(position) => { self.select_previous() }
+            _ => {}
+        }
+    }
+
+    // Selects the row, clicking the [ ] mark toggles it.
+    fn click_list_row(&mut self, position: Position) {
+        let list_area = self.clickable_areas.list;
+        let index = self.active_tab_storage.list_state.offset() + (position.y - list_area.y) as usize;
+        let list_length = match self.selected_tab {
+            SelectedTab::Tab5 => self.active_tab_storage.steam_libraries.len(),
+            _ => self.get_listed_app_names().len(),
+        };
+        if index >= list_length {
+            return;
+        }
+        if self.active_tab_storage.list_state.selected() != Some(index) {
+            self.active_tab_storage.list_state.select(Some(index));
+            self.release_notes_scroll = 0;
+        }
+
+        // "> [x] ..."
+        let clicked_mark = position.x < list_area.x + 5;
+        if clicked_mark && matches!(self.selected_tab, SelectedTab::Tab1 | SelectedTab::Tab2 | SelectedTab::Tab3) {
+            self.mark_selected_mod();
+        }
     }
-    fn patch(&mut self) {
+
+    fn select_next(&mut self) {
         self.active_tab_storage.list_state.select_next();
+        self.release_notes_scroll = 0;
     }
     fn select_previous(&mut self) {
         self.active_tab_storage.list_state.select_previous();
+        self.release_notes_scroll = 0;
     }
 
     fn select_first(&mut self) {
         self.active_tab_storage.list_state.select_first();
+        self.release_notes_scroll = 0;
     }
 
     fn select_last(&mut self) {
         self.active_tab_storage.list_state.select_last();
+        self.release_notes_scroll = 0;
     }
 
     // Tabs
     fn next_tab(&mut self) {
+        if self.ask_before_discarding(DiscardableAction::SelectTab(self.selected_tab.next())) {
+            return;
+        }
         let prev = self.selected_tab;
         self.selected_tab = self.selected_tab.next();
         if prev.to_string() != self.selected_tab.to_string() {
@@ -366,7 +742,21 @@ impl App {
         }
     }
 
+    fn select_tab(&mut self, tab: SelectedTab) {
+        if self.ask_before_discarding(DiscardableAction::SelectTab(tab)) {
+            return;
+        }
+        let prev = self.selected_tab;
+        self.selected_tab = tab;
+        if prev.to_string() != self.selected_tab.to_string() {
+            self.reset_active_tab_storage();
+        }
+    }
+
     fn previous_tab(&mut self) {
+        if self.ask_before_discarding(DiscardableAction::SelectTab(self.selected_tab.previous())) {
+            return;
+        }
         let prev = self.selected_tab;
         self.selected_tab = self.selected_tab.previous();
         if prev.to_string() != self.selected_tab.to_string() {
@@ -380,14 +770,84 @@ impl App {
     fn reset_active_tab_storage(&mut self) {
         self.active_tab_storage = TabStorage::default();
         self.reload_config();
+        if let SelectedTab::Tab5 = self.selected_tab {
+            match get_detected_steam_libraries() {
+                Ok(steam_libraries) => { self.active_tab_storage.steam_libraries = steam_libraries }
+                Err(e) => { self.error_popup = Some(format!("Error detecting the Steam libraries: '{e}'")) }
+            }
+        }
         // self.reload_config();
         // self.active_tab_storage.config_manager = self.config_manager.clone();
     }
 
     fn quit(&mut self) {
+        if self.ask_before_discarding(DiscardableAction::Quit) {
+            return;
+        }
         self.running_state = AppState::Quitting;
     }
 
+    // Opens the prompt instead if there are unsaved changes, returns whether it did.
+    fn ask_before_discarding(&mut self, discardable_action: DiscardableAction) -> bool {
+        let discards_changes = match discardable_action {
+            DiscardableAction::SelectTab(tab) => tab != self.selected_tab,
+            DiscardableAction::Quit | DiscardableAction::Reload => true,
+        };
+        if !self.unsaved_changes || !discards_changes {
+            return false;
+        }
+        let consequence = match discardable_action {
+            DiscardableAction::Quit => "Quitting throws them away.".to_string(),
+            DiscardableAction::SelectTab(tab) => format!("Opening the tab '{tab}' reloads the configuration from disk, throwing them away."),
+            DiscardableAction::Reload => "Reloading the configuration from disk throws them away.".to_string(),
+        };
+        self.dialog = Some(Dialog {
+            title: "Unsaved changes".to_string(),
+            summary: vec!["The mods enabled/disabled haven't been saved.".to_string(), consequence],
+            buttons: vec![
+                DialogButton { label: "Save (s)", key: 's', action: DialogAction::SaveAndThen(discardable_action) },
+                DialogButton { label: "Discard (d)", key: 'd', action: DialogAction::DiscardAndThen(discardable_action) },
+                DialogButton { label: "Cancel (c/Esc)", key: 'c', action: DialogAction::Cancel },
+            ],
+            selected_button: 2,
+        });
+        true
+    }
+
+    fn pick_dialog_button(&mut self, button_index: usize) {
+        let Some(dialog) = self.dialog.take() else { return };
+        let Some(button) = dialog.buttons.into_iter().nth(button_index) else { return };
+        match button.action {
+            DialogAction::Cancel => {}
+            DialogAction::UpdateApps(app_names) => { self.current_sub_menu = SubMenus::UpdateApps(app_names) }
+            DialogAction::ReinstallApp(app_name) => self.reinstall_app(app_name),
+            DialogAction::UninstallApps(app_names) => self.uninstall_apps(app_names),
+            DialogAction::PatchApps(app_names) => self.patch_apps(app_names),
+            DialogAction::SwitchProfile(profile_name) => self.switch_profile(profile_name),
+            DialogAction::RestoreGameFiles => self.restore_game_files(),
+            DialogAction::SaveAndThen(discardable_action) => {
+                self.save_config();
+                // Nothing is thrown away if the save failed
+                if !self.unsaved_changes {
+                    self.run_discardable_action(discardable_action);
+                }
+            }
+            DialogAction::DiscardAndThen(discardable_action) => {
+                self.unsaved_changes = false;
+                self.run_discardable_action(discardable_action);
+            }
+        }
+    }
+
+    // Once the changes were saved or discarded.
+    fn run_discardable_action(&mut self, discardable_action: DiscardableAction) {
+        match discardable_action {
+            DiscardableAction::Quit => self.quit(),
+            DiscardableAction::SelectTab(tab) => self.select_tab(tab),
+            DiscardableAction::Reload => self.reload_config(),
+        }
+    }
+
 
     // fn download_mod(&mut self, app_name: String) {
     //
@@ -404,24 +864,182 @@ impl App {
     // }
 
     fn update_all_enabled_mods(&mut self) {
-        self.current_sub_menu=SubMenus::UpdateAllApps; // Select menu to render
+        let app_names = self.active_tab_storage.get_enabled_app_names();
+        self.confirm_update(app_names);
+    }
+
+    fn update_selected_mod(&mut self) {
+        let app_names = self.active_tab_storage.get_target_app_names(&self.get_listed_app_names());
+        self.confirm_update(app_names);
+    }
+
+    fn confirm_update(&mut self, app_names: Vec<String>) {
+        if app_names.is_empty() {
+            return;
+        }
+        let mut summary: Vec<String> = vec![];
+        for app_name in &app_names {
+            let Some(app) = self.active_tab_storage.config_manager.config.apps.get(app_name) else { continue };
+            summary.push(match self.latest_pulled_tags_hashmap.get(app_name) {
+                None => format!("{app_name}: skipped, search updates first"),
+                Some(cached_release) if !app.has_new_version(&cached_release.tag_info) => format!("{app_name}: skipped, no new version found"),
+                Some(cached_release) => format!("{app_name}: '{}' -> '{}', replacing its mod folder", app.tag_name, cached_release.tag_info.tag_name),
+            });
+        }
+        self.dialog = Some(Dialog::confirm(format!("Update {} mod(s)?", app_names.len()), summary, DialogAction::UpdateApps(app_names)));
+    }
+
+    // Apps listed in the current tab.
+    fn get_listed_app_names(&self) -> Vec<String> {
+        match self.selected_tab {
+            SelectedTab::Tab1 => self.active_tab_storage.get_filtered_app_names(self.active_tab_storage.get_app_names(), &self.latest_pulled_tags_hashmap),
+            SelectedTab::Tab2 => self.active_tab_storage.get_filtered_app_names(self.active_tab_storage.get_enabled_app_names(), &self.latest_pulled_tags_hashmap),
+            _ => self.active_tab_storage.get_enabled_app_names(),
+        }
+    }
+
+    fn edit_list_filter(&mut self, edit: impl FnOnce(&mut ListFilter)) {
+        edit(&mut self.active_tab_storage.list_filter);
+        self.select_first();
+        self.release_notes_scroll = 0;
+    }
+
+    fn cycle_app_type_filter(&mut self) {
+        let mut app_types: Vec<AppType> = vec![];
+        for app in self.active_tab_storage.config_manager.config.apps.values() {
+            if !app_types.contains(&app.app_type) {
+                app_types.push(app.app_type.clone());
+            }
+        }
+        app_types.sort_by_key(|app_type| app_type.to_string());
+        self.edit_list_filter(|list_filter| list_filter.app_type = get_next_option(&app_types, &list_filter.app_type));
+    }
+
+    fn cycle_repo_owner_filter(&mut self) {
+        let repo_owners: Vec<String> = self.active_tab_storage.config_manager.config.apps.values()
+            .map(|app| app.repo_owner.to_string())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        self.edit_list_filter(|list_filter| list_filter.repo_owner = get_next_option(&repo_owners, &list_filter.repo_owner));
+    }
+
+    fn mark_selected_mod(&mut self) {
+        let app_names = self.get_listed_app_names();
+        self.active_tab_storage.toggle_mark(&app_names);
+    }
+
+    fn mark_all_mods(&mut self) {
+        let app_names = self.get_listed_app_names();
+        self.active_tab_storage.toggle_mark_all(&app_names);
+    }
+
+    fn uninstall_selected_mods(&mut self) {
+        let app_names = self.active_tab_storage.get_target_app_names(&self.get_listed_app_names());
+        if app_names.is_empty() {
+            return;
+        }
+        let config_manager = &self.active_tab_storage.config_manager;
+        let mut summary: Vec<String> = vec![];
+        for app_name in &app_names {
+            match config_manager.get_mod_dir_path(app_name) {
+                Ok(mod_dir_path) => summary.push(format!("{app_name}: removes '{mod_dir_path}'")),
+                Err(e) => summary.push(format!("{app_name}: {e}")),
+            }
+        }
+        let patched_app_names: Vec<String> = config_manager.config.apps.values().filter(|app| app.patched).map(|app| app.get_app_name()).sorted().collect();
+        if patched_app_names.iter().any(|app_name| app_names.contains(app_name)) {
+            match self.get_restore_summary() {
+                Ok(restore_summary) => {
+                    summary.extend(restore_summary);
+                    let app_names_to_patch_again: Vec<String> = patched_app_names.into_iter().filter(|app_name| !app_names.contains(app_name)).collect();
+                    if !app_names_to_patch_again.is_empty() {
+                        summary.push(format!("Patches again: {}", app_names_to_patch_again.join(", ")));
+                    }
+                }
+                // Same as Manager::uninstall_apps
+                Err(e) => {
+                    summary.push(format!("GuiltyGearXrd.exe isn't restored and stays patched by the uninstalled mods: {e}"));
+                    summary.extend(self.get_removed_patch_files_summary(|app_name| app_names.contains(app_name)));
+                }
+            }
+        }
+        self.dialog = Some(Dialog::confirm(format!("Uninstall {} mod(s)?", app_names.len()), summary, DialogAction::UninstallApps(app_names)));
+    }
+
+    fn uninstall_apps(&mut self, app_names: Vec<String>) {
+        match self.active_tab_storage.config_manager.uninstall_apps(&app_names) {
+            Ok((patch_results, warning)) => {
+                let mut error_lines: Vec<String> = warning.into_iter().collect();
+                for (app_name, patch_result) in patch_results {
+                    match patch_result {
+                        Ok(patch_output) => { self.patch_outputs_hashmap.insert(app_name, patch_output); }
+                        Err(e) => error_lines.push(format!("Error patching app '{app_name}' again: '{e}'")),
+                    }
+                }
+                for app_name in &app_names {
+                    self.pulled_changelogs_hashmap.remove(app_name);
+                }
+                self.active_tab_storage.marked_app_names.clear();
+                if !error_lines.is_empty() {
+                    self.error_popup = Some(error_lines.join("\n"));
+                }
+                self.save_config()
+            }
+            Err(e) => { self.error_popup = Some(format!("Error uninstalling '{}': '{e}'", app_names.join("', '"))) }
+        }
+    }
+
+    fn reinstall_selected_mod(&mut self) {
+        let Some(app_name) = self.active_tab_storage.get_target_app_names(&self.get_listed_app_names()).into_iter().next() else { return };
+        let config_manager = &self.active_tab_storage.config_manager;
+        let Some(app) = config_manager.config.apps.get(&app_name) else { return };
+        let mod_dir_path = config_manager.get_mod_dir_path(&app_name).unwrap_or_default();
+        let summary = match app.is_local_install() {
+            true => vec![format!("{app_name}: replaces the local build in '{mod_dir_path}' with the latest release")],
+            false => vec![format!("{app_name}: downloads '{}' again, replacing '{mod_dir_path}'", app.tag_name)],
+        };
+        self.dialog = Some(Dialog::confirm(format!("Reinstall '{app_name}'?"), summary, DialogAction::ReinstallApp(app_name)));
+    }
+
+    fn reinstall_app(&mut self, app_name: String) {
+        match self.active_tab_storage.config_manager.reinstall_app(app_name.to_string()) {
+            Ok(_) => {
+                self.pulled_changelogs_hashmap.remove(&app_name);
+                self.save_config()
+            }
+            Err(e) => { self.error_popup = Some(format!("Error reinstalling app '{app_name}': '{e}'")) }
+        }
     }
 
     fn pull_latest_tags(&mut self) {
         // let mut tags_hashmap:HashMap<String, TagInfo> = HashMap::new();
+        let mut error_lines: Vec<String> = vec![];
         for app_name in self.active_tab_storage.get_enabled_app_names() {
-            let result = self.active_tab_storage.config_manager.config.apps.get(&app_name).unwrap().get_latest_tag();
+            let result = self.active_tab_storage.config_manager.get_latest_release(&app_name);
             match result {
                 Ok(new_tag) => {
+                    match self.active_tab_storage.config_manager.get_releases_since_installed(&app_name, &new_tag.tag_info) {
+                        Ok(changelog) => { self.pulled_changelogs_hashmap.insert(app_name.to_string(), changelog); }
+                        // Only the latest release notes are shown then.
+                        Err(e) => {
+                            self.pulled_changelogs_hashmap.remove(&app_name);
+                            if !matches!(e, XrdError::NotCached(_)) {
+                                error_lines.push(format!("Error getting the releases of app '{}': << {} >>", app_name, e));
+                            }
+                        }
+                    }
                     self.latest_pulled_tags_hashmap.insert(app_name, new_tag);
                 }
                 Err(e) => {
-                    // println!("Error getting tag for app '{}': << {} >>", app_struct.get_app_name(), e);
-                    // exit(1);
+                    error_lines.push(format!("Error getting tag for app '{}': << {} >>", app_name, e));
                 }
             }
         // self.latest_tags_pulled_map
         }
+        if !error_lines.is_empty() {
+            self.error_popup = Some(error_lines.join("\n"));
+        }
     }
     fn reload_config(&mut self) {
         // self.config_manager=Manager::default();
@@ -429,27 +1047,239 @@ impl App {
         // self.active_tab_storage = TabStorage::default();
         // self.reload_config();
         self.active_tab_storage.config_manager = Manager::default();
-        self.active_tab_storage.config_manager.load_config();
+        self.unsaved_changes = false;
+        if let Err(e) = self.active_tab_storage.config_manager.load_config() {
+            self.error_popup = Some(format!("Error loading the configuration: '{e}'"));
+        }
+        match Theme::load(&self.active_tab_storage.config_manager.config.theme) {
+            Ok(theme) => { set_theme(theme) }
+            Err(e) => {
+                set_theme(Theme::dark());
+                self.error_popup = Some(format!("Error loading the theme: '{e}'"));
+            }
+        }
+        // The default keys are kept until the keybindings are fixed.
+        let action_groups: Vec<&[Action]> = SelectedTab::iter().map(SelectedTab::get_actions).collect();
+        match Keymap::from_config(&self.active_tab_storage.config_manager.config.keybindings, &action_groups) {
+            Ok(keymap) => { self.keymap = keymap }
+            Err(e) => {
+                self.keymap = Keymap::default();
+                self.error_popup = Some(format!("Error loading the keybindings: '{e}'"));
+            }
+        }
     }
 
     fn save_config(&mut self) {
-        self.active_tab_storage.config_manager.save_config();
+        match self.active_tab_storage.config_manager.save_config() {
+            Ok(_) => { self.unsaved_changes = false }
+            Err(e) => { self.error_popup = Some(format!("Error saving the configuration: '{e}'")) }
+        }
     }
 
 
+    // Tab 3
+    fn patch_selected_mods(&mut self) {
+        let app_names = self.active_tab_storage.get_target_app_names(&self.active_tab_storage.get_enabled_app_names());
+        if app_names.is_empty() {
+            return;
+        }
+        let config = &self.active_tab_storage.config_manager.config;
+        let mut summary: Vec<String> = vec![match config.xrd_game_folder.is_empty() {
+            true => "Game folder: detected from the Steam libraries".to_string(),
+            false => format!("Game folder: '{}'", config.xrd_game_folder),
+        }];
+        for app_name in &app_names {
+            let Some(app) = config.apps.get(app_name) else { continue };
+            if !app.is_patchable() {
+                summary.push(format!("{app_name}: skipped, it doesn't have a patch procedure"));
+                continue;
+            }
+            let (files_to_copy, file_to_execute) = app.get_patch_files();
+            let mut changes: Vec<String> = vec![];
+            if !files_to_copy.is_empty() {
+                changes.push(format!("copies {} to Binaries/Win32", files_to_copy.join(", ")));
+            }
+            if !file_to_execute.is_empty() {
+                changes.push(format!("runs {file_to_execute} to patch GuiltyGearXrd.exe"));
+            }
+            summary.push(format!("{app_name}: {}{}", changes.join(" and "), if app.patched { " (already patched)" } else { "" }));
+        }
+        self.dialog = Some(Dialog::confirm(format!("Patch {} mod(s)?", app_names.len()), summary, DialogAction::PatchApps(app_names)));
+    }
+
+    fn patch_apps(&mut self, app_names: Vec<String>) {
+        let mut error_lines: Vec<String> = vec![];
+        for app_name in app_names {
+            match self.active_tab_storage.config_manager.patch_app(app_name.to_string()) {
+                Ok(patch_output) => {
+                    self.patch_outputs_hashmap.insert(app_name, patch_output);
+                    self.save_config();
+                }
+                Err(e) => {
+                    error_lines.push(format!("Error when patching app '{app_name}': '{e}'"));
+                }
+            }
+        }
+        self.active_tab_storage.marked_app_names.clear();
+        if !error_lines.is_empty() {
+            self.error_popup = Some(error_lines.join("\n"));
+        }
+    }
+
+    // What restoring the unpatched game files does, an error if the backup can't be restored.
+    fn get_restore_summary(&mut self) -> XrdResult<Vec<String>> {
+        let backup_file_path = self.active_tab_storage.config_manager.get_valid_game_backup()?;
+        let mut summary: Vec<String> = vec![format!("Restores GuiltyGearXrd.exe from the backup '{backup_file_path}'")];
+        summary.extend(self.get_removed_patch_files_summary(|_| true));
+        Ok(summary)
+    }
+
+    // Files copied to the game folder by the patched apps, removed when reverting their patches.
+    fn get_removed_patch_files_summary(&self, filter: impl Fn(&String) -> bool) -> Vec<String> {
+        let config = &self.active_tab_storage.config_manager.config;
+        let mut summary: Vec<String> = vec![];
+        for app in config.apps.values().filter(|app| app.patched && filter(&app.get_app_name())).sorted_by_key(|app| app.get_app_name()) {
+            let (files_to_copy, _) = app.get_patch_files();
+            match files_to_copy.is_empty() {
+                true => summary.push(format!("{}: marked as not patched", app.get_app_name())),
+                false => summary.push(format!("{}: removes {} from Binaries/Win32", app.get_app_name(), files_to_copy.join(", "))),
+            }
+        }
+        summary
+    }
+
+    fn confirm_restore_game_files(&mut self) {
+        match self.get_restore_summary() {
+            Ok(summary) => { self.dialog = Some(Dialog::confirm("Restore the unpatched game files?".to_string(), summary, DialogAction::RestoreGameFiles)) }
+            Err(e) => { self.error_popup = Some(format!("Error restoring the game files: '{e}'")) }
+        }
+    }
+
+    fn restore_game_files(&mut self) {
+        match self.active_tab_storage.config_manager.restore_game_files() {
+            Ok(_) => {
+                self.patch_outputs_hashmap.clear();
+                self.save_config();
+            }
+            Err(e) => { self.error_popup = Some(format!("Error restoring the game files: '{e}'")) }
+        }
+    }
+
+    // Tab 5
+    fn set_game_folder(&mut self, xrd_game_folder: String) {
+        match self.active_tab_storage.config_manager.config.set_xrd_game_folder(xrd_game_folder) {
+            Ok(_) => { self.save_config() }
+            Err(e) => { self.error_popup = Some(e.to_string()) }
+        }
+    }
+
+    fn set_game_folder_from_input(&mut self) {
+        if let Some(xrd_game_folder) = self.game_folder_input.take() {
+            self.set_game_folder(xrd_game_folder.trim().to_string());
+        }
+    }
+
+    // Goes through the built-in themes, a theme file is replaced by the first one.
+    fn switch_to_next_theme(&mut self) {
+        let theme_names = get_built_in_theme_names();
+        let config = &mut self.active_tab_storage.config_manager.config;
+        let current_theme_name = if config.theme.is_empty() { DEFAULT_THEME_NAME } else { config.theme.as_str() };
+        let next_index = theme_names.iter().position(|theme_name| *theme_name == current_theme_name).map_or(0, |index| (index + 1) % theme_names.len());
+        config.theme = theme_names[next_index].to_string();
+        match Theme::load(&config.theme) {
+            Ok(theme) => { set_theme(theme) }
+            Err(e) => { self.error_popup = Some(format!("Error loading the theme: '{e}'")) }
+        }
+        self.save_config();
+    }
+
+    fn toggle_offline_mode(&mut self) {
+        self.active_tab_storage.config_manager.config.offline_mode ^= true;
+        self.save_config();
+    }
+
+    fn switch_to_next_profile(&mut self) {
+        let profile_names = self.active_tab_storage.config_manager.config.get_profile_names();
+        if profile_names.len() < 2 {
+            self.error_popup = Some("There is only one profile, save another one with 'profile save <NAME>'".to_string());
+            return;
+        }
+        let active_profile = self.active_tab_storage.config_manager.config.active_profile.to_string();
+        let next_index = profile_names.iter().position(|profile_name| *profile_name == active_profile).map_or(0, |index| (index + 1) % profile_names.len());
+        let next_profile = profile_names[next_index].to_string();
+
+        let config = &self.active_tab_storage.config_manager.config;
+        let Some(profile) = config.profiles.get(&next_profile).cloned() else { return };
+        let current_profile = config.get_current_profile();
+        let get_changes = |app_names: Vec<&String>| match app_names.is_empty() {
+            true => "none".to_string(),
+            false => app_names.into_iter().sorted().join(", "),
+        };
+        let mut summary: Vec<String> = vec![
+            format!("Enables: {}", get_changes(profile.enabled_apps.iter().filter(|app_name| !current_profile.enabled_apps.contains(app_name)).collect())),
+            format!("Disables: {}", get_changes(current_profile.enabled_apps.iter().filter(|app_name| !profile.enabled_apps.contains(app_name)).collect())),
+        ];
+        // Same as Manager::switch_profile
+        let only_adds_patches = current_profile.patched_apps.iter().all(|app_name| profile.patched_apps.contains(app_name));
+        if !only_adds_patches {
+            match self.get_restore_summary() {
+                Ok(restore_summary) => summary.extend(restore_summary),
+                Err(e) => {
+                    self.error_popup = Some(format!("Error switching to the profile '{next_profile}': '{e}'"));
+                    return;
+                }
+            }
+        }
+        let app_names_to_patch: Vec<&String> = profile.patched_apps.iter().filter(|app_name| !only_adds_patches || !current_profile.patched_apps.contains(app_name)).collect();
+        summary.push(format!("Patches: {}", get_changes(app_names_to_patch)));
+        self.dialog = Some(Dialog::confirm(format!("Switch to the profile '{next_profile}'?"), summary, DialogAction::SwitchProfile(next_profile)));
+    }
+
+    fn switch_profile(&mut self, next_profile: String) {
+        let switch_result = self.active_tab_storage.config_manager.switch_profile(next_profile.to_string());
+        let mut error_lines: Vec<String> = vec![];
+        match switch_result {
+            Ok(patch_results) => {
+                for (app_name, patch_result) in patch_results {
+                    match patch_result {
+                        Ok(patch_output) => { self.patch_outputs_hashmap.insert(app_name, patch_output); }
+                        Err(e) => error_lines.push(format!("Error when patching app '{app_name}': '{e}'")),
+                    }
+                }
+            }
+            Err(e) => error_lines.push(format!("Error switching to profile '{next_profile}': '{e}'")),
+        }
+        self.save_config();
+        if !error_lines.is_empty() {
+            self.error_popup = Some(error_lines.join("\n"));
+        }
+    }
+
+    fn set_game_folder_from_selected_library(&mut self) {
+        if let Some(index) = self.active_tab_storage.list_state.selected() {
+            match self.active_tab_storage.steam_libraries.get(index) {
+                Some(library) if library.has_xrd => { self.set_game_folder(library.get_xrd_game_folder()) }
+                Some(library) => { self.error_popup = Some(format!("Guilty Gear Xrd isn't installed in the Steam library '{}'", library.path)) }
+                None => {}
+            }
+        }
+    }
+
     // Tab 1
     fn toggle_enable_disable_mod(&mut self) {
         // println!("{:?}", self.tab_storage.list_state.selected());
         // sleep_ms(1000000);
-        match self.active_tab_storage.list_state.selected() {
-            Some(index) => {
-                let app_list = self.active_tab_storage.get_app_names(); // self.config_manager.get_sorted_apps_name();
-                let app = self.active_tab_storage.config_manager.config.apps.get_mut(app_list.get(index).unwrap()).unwrap();
-                app.enabled ^= true;
-                // let app = self.active_tab_storage.get_sorted_apps_name();
+        let app_names = self.active_tab_storage.get_target_app_names(&self.get_listed_app_names());
+        // Marked apps are all enabled, unless they already are.
+        let apps = &mut self.active_tab_storage.config_manager.config.apps;
+        let enable = !app_names.iter().all(|app_name| apps.get(app_name).is_some_and(|app| app.enabled));
+        for app_name in &app_names {
+            if let Some(app) = apps.get_mut(app_name) {
+                self.unsaved_changes |= app.enabled != enable;
+                app.enabled = enable;
             }
-            _ => {}
         }
+        self.active_tab_storage.marked_app_names.clear();
         // use thread::sleep_ms;
         // sleep_ms(111111111);
     }
@@ -458,7 +1288,11 @@ impl App {
 impl App {
     fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
         let titles = SelectedTab::iter().map(SelectedTab::title);
-        let highlight_style = (Color::default(), self.selected_tab.palette().c700);
+        let theme = get_theme();
+        // Bold and underlined too, so it doesn't rely on the color alone.
+        let highlight_style = Style::new()
+            .bg(theme.tab_highlight_bg.unwrap_or(self.selected_tab.palette().c700))
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
         let selected_tab_index = self.selected_tab as usize;
         Tabs::new(titles)
             .highlight_style(highlight_style)
@@ -472,9 +1306,10 @@ impl App {
 impl SelectedTab {
     /// Return tab's name as a styled `Line`
     fn title(self) -> Line<'static> {
+        let theme = get_theme();
         format!("  {self}  ")
-            .fg(tailwind::SLATE.c200)
-            .bg(self.palette().c900)
+            .fg(theme.tab_fg)
+            .bg(theme.tab_bg.unwrap_or(self.palette().c900))
             .into()
     }
     fn block(self) -> Block<'static> {
@@ -490,6 +1325,50 @@ impl SelectedTab {
             Self::Tab2 => tailwind::EMERALD,
             Self::Tab3 => tailwind::INDIGO,
             Self::Tab4 => tailwind::RED,
+            Self::Tab5 => tailwind::AMBER,
+        }
+    }
+
+    // Actions available in the tab, in the order listed by the help.
+    fn get_actions(self) -> &'static [Action] {
+        match self {
+            Self::Tab1 => &[
+                Action::ToggleEnabled, Action::Mark, Action::MarkAll,
+                Action::Search, Action::FilterOutdated, Action::FilterPatched, Action::FilterType, Action::FilterOwner, Action::ClearFilters,
+                Action::Save, Action::Reload,
+                Action::SelectNext, Action::SelectPrevious, Action::SelectFirst, Action::SelectLast,
+                Action::NextTab, Action::PreviousTab, Action::Help, Action::Quit,
+            ],
+            Self::Tab2 => &[
+                Action::SearchUpdates, Action::UpdateSelected, Action::UpdateAll, Action::Reinstall, Action::Uninstall,
+                Action::Mark, Action::MarkAll,
+                Action::Search, Action::FilterOutdated, Action::FilterPatched, Action::FilterType, Action::FilterOwner, Action::ClearFilters,
+                Action::ScrollNotesDown, Action::ScrollNotesUp, Action::Reload,
+                Action::SelectNext, Action::SelectPrevious, Action::SelectFirst, Action::SelectLast,
+                Action::NextTab, Action::PreviousTab, Action::Help, Action::Quit,
+            ],
+            Self::Tab3 => &[
+                Action::Patch, Action::RestoreGameFiles, Action::Mark, Action::MarkAll,
+                Action::SelectNext, Action::SelectPrevious, Action::SelectFirst, Action::SelectLast,
+                Action::NextTab, Action::PreviousTab, Action::Help, Action::Quit,
+            ],
+            Self::Tab4 => &[Action::NextTab, Action::PreviousTab, Action::Help, Action::Quit],
+            Self::Tab5 => &[
+                Action::UseSelectedLibrary, Action::EditGameFolder, Action::DetectGameFolder, Action::SwitchProfile, Action::ToggleOfflineMode, Action::SwitchTheme, Action::Reload,
+                Action::SelectNext, Action::SelectPrevious, Action::SelectFirst, Action::SelectLast,
+                Action::NextTab, Action::PreviousTab, Action::Help, Action::Quit,
+            ],
+        }
+    }
+
+    // Shown in the footer, the rest are listed by the help.
+    fn get_footer_actions(self) -> &'static [Action] {
+        match self {
+            Self::Tab1 => &[Action::Help, Action::ToggleEnabled, Action::Mark, Action::Search, Action::Save, Action::Quit],
+            Self::Tab2 => &[Action::Help, Action::SearchUpdates, Action::UpdateSelected, Action::UpdateAll, Action::Search, Action::Quit],
+            Self::Tab3 => &[Action::Help, Action::Patch, Action::RestoreGameFiles, Action::Mark, Action::Quit],
+            Self::Tab4 => &[Action::Help, Action::NextTab, Action::PreviousTab, Action::Quit],
+            Self::Tab5 => &[Action::Help, Action::UseSelectedLibrary, Action::EditGameFolder, Action::SwitchProfile, Action::Quit],
         }
     }
 }
@@ -511,18 +1390,52 @@ impl Widget for &mut App {
         let horizontal = Layout::horizontal([Min(0), Length(20)]);
         let [tabs_area, title_area] = horizontal.areas(header_area);
 
-        render_title(title_area, buf);
+        let theme = get_theme();
+        buf.set_style(area, Style::new().fg(theme.text).bg(theme.background));
+        render_title(title_area, buf, self.unsaved_changes);
         self.render_tabs(tabs_area, buf);
+        self.clickable_areas = ClickableAreas {
+            tabs: get_tab_areas(tabs_area),
+            ..ClickableAreas::default()
+        };
 
 
         match self.selected_tab {
-            SelectedTab::Tab1 => self.selected_tab.render_enable_mods_tab(inner_area, buf, &mut self.active_tab_storage),
+            SelectedTab::Tab1 => {
+                let app_names = self.get_listed_app_names();
+                let list_area = render_list_filter(inner_area, buf, &self.active_tab_storage.list_filter);
+                self.clickable_areas.list = list_area;
+                self.selected_tab.render_enable_mods_tab(list_area, buf, &mut self.active_tab_storage, &app_names);
+            },
             SelectedTab::Tab2 => {
                 let split_inner_area_vertical = Layout::horizontal([Min(0), Min(0)]);
                 let [main_content_area, bottom_content_area] = split_inner_area_vertical.areas(inner_area);
 
-                self.selected_tab.render_update_mods_tab(main_content_area, buf, &mut self.active_tab_storage, &mut self.latest_pulled_tags_hashmap);
-                self.selected_tab.describe_selected_mod_tag_description(bottom_content_area, buf, &mut self.active_tab_storage, &mut self.latest_pulled_tags_hashmap);
+                let app_names = self.get_listed_app_names();
+                let list_area = render_list_filter(main_content_area, buf, &self.active_tab_storage.list_filter);
+                self.clickable_areas.list = list_area;
+                self.clickable_areas.release_notes = bottom_content_area;
+                self.selected_tab.render_update_mods_tab(list_area, buf, &mut self.active_tab_storage, &app_names, &mut self.latest_pulled_tags_hashmap);
+                let selected_app = self.active_tab_storage.list_state.selected()
+                    .and_then(|index| app_names.get(index))
+                    .and_then(|app_name| self.active_tab_storage.config_manager.config.apps.get(app_name));
+                self.selected_tab.describe_selected_mod_tag_description(bottom_content_area, buf, selected_app, &mut self.latest_pulled_tags_hashmap, &self.pulled_changelogs_hashmap, &mut self.release_notes_scroll);
+            },
+            SelectedTab::Tab3 => {
+                let split_inner_area_vertical = Layout::horizontal([Min(0), Min(0)]);
+                let [main_content_area, bottom_content_area] = split_inner_area_vertical.areas(inner_area);
+
+                self.clickable_areas.list = main_content_area;
+                self.selected_tab.render_patch_mods_tab(main_content_area, buf, &mut self.active_tab_storage);
+                self.selected_tab.describe_selected_mod_patch_output(bottom_content_area, buf, &mut self.active_tab_storage, &self.patch_outputs_hashmap);
+            },
+            SelectedTab::Tab5 => {
+                let split_inner_area_vertical = Layout::vertical([Length(7), Min(0)]);
+                let [game_folder_area, libraries_area] = split_inner_area_vertical.areas(inner_area);
+
+                self.selected_tab.render_game_folder_settings(game_folder_area, buf, &mut self.active_tab_storage, &self.game_folder_input);
+                self.clickable_areas.list = libraries_area.inner(Margin::new(1, 1));
+                self.selected_tab.render_steam_libraries(libraries_area, buf, &mut self.active_tab_storage);
             },
             _ => {
                 //println!("tab out of bounds!")
@@ -534,24 +1447,14 @@ impl Widget for &mut App {
         // Could be used to read errors -> then render the error popup.
         match self.current_sub_menu  {
             // Update "submenu"
-            SubMenus::UpdateSingleApps|SubMenus::UpdateAllApps => {
+            SubMenus::UpdateApps(ref app_names) => {
                 // Update
 
                 // Reset hmap
                 self.update_apps_status_hashmap = HashMap::new();
 
                 // Get app list
-                let mut app_name_list: Vec<String> = match self.current_sub_menu {
-                    SubMenus::UpdateSingleApps => {
-                        match self.active_tab_storage.list_state.selected() {
-                            None => {vec![]} // TODO if no app is selected this shouldn't be rendered when UpdateSingleApp
-                            Some(index) => {
-                                vec![self.active_tab_storage.get_enabled_app_names().get(index).unwrap().to_string()]
-                            }
-                        }
-                    }
-                    _ => { self.active_tab_storage.get_enabled_app_names()}
-                };
+                let app_name_list: Vec<String> = app_names.clone();
                 // Format
                 // Apps to update:
                 // - 1 (waiting)
@@ -584,8 +1487,23 @@ impl Widget for &mut App {
                     // If no tag found custom message or something idk.
                     // crate::IntellijRustImportsMock::render_update_status(&update_apps_status_hashmap, popup_area, buf);
 
-                    // Get latest tag
-                    let latest_tag_info = self.latest_pulled_tags_hashmap.get(&app_name).unwrap();
+                    // Get latest tag, skipping the apps without a new version
+                    let latest_tag_info = match self.latest_pulled_tags_hashmap.get(&app_name) {
+                        None => {
+                            if let Some(app_update_status) = self.update_apps_status_hashmap.get_mut(&app_name) {
+                                app_update_status.status = AppUpdatingStatusStatus::NotChecked;
+                            }
+                            continue;
+                        }
+                        Some(cached_release) => &cached_release.tag_info,
+                    };
+                    let has_new_version = self.active_tab_storage.config_manager.config.apps.get(&app_name).is_some_and(|app| app.has_new_version(latest_tag_info));
+                    if !has_new_version {
+                        if let Some(app_update_status) = self.update_apps_status_hashmap.get_mut(&app_name) {
+                            app_update_status.status = AppUpdatingStatusStatus::UpToDate;
+                        }
+                        continue;
+                    }
 
                     // Attempt to download the latest tag
                     match self.active_tab_storage.config_manager.update_app(app_name.to_string(), latest_tag_info) {
@@ -598,6 +1516,8 @@ impl Widget for &mut App {
                                     app_update_status.status = AppUpdatingStatusStatus::Updated;
                                 }
                             }
+                            // Up to date, only the latest notes are left to show.
+                            self.pulled_changelogs_hashmap.remove(&app_name);
                             self.save_config() // TODO reenable, testing
                         },
                         Err(e) => {
@@ -607,10 +1527,7 @@ impl Widget for &mut App {
                                     app_update_status.status = AppUpdatingStatusStatus::Failed;
                                 }
                             }
-                        }
-                        _ => {
-                            // Err(e) => {
-                            // Stop and print error
+                            self.error_popup = Some(format!("Error updating app '{app_name}': '{e}'"));
                         }
                     }
                     // Only if it works >:(, if error Render error and break the loop.
@@ -620,17 +1537,18 @@ impl Widget for &mut App {
                 // let text = Text::from("hello");
                 // let bad_popup = Paragraph::new(text)
                 //     .wrap(Wrap { trim: true })
-                //     .style(Style::new().yellow())
+                //     .style(Style::new().fg(theme.warning))
                 //     .block(
                 //         Block::new()
                 //             .title("Updating apps")
-                //             .title_style(Style::new().white().bold())
+                //             .title_style(Style::new().fg(theme.text).bold())
                 //             .borders(Borders::ALL)
-                //             .border_style(Style::new().red()),
+                //             .border_style(Style::new().fg(theme.error)),
                 //     );
                 // let x = Widget::render(bad_popup, popup_area, buf);
                 // x.blink();
 
+                self.active_tab_storage.marked_app_names.clear();
                 // self.current_sub_menu=SubMenus::None;
                 // Mark completed
                 self.current_sub_menu=SubMenus::UpdateAllCompleted;
@@ -638,7 +1556,8 @@ impl Widget for &mut App {
 
                 // Render menu
                 fn render_update_status(update_apps_status_hashmap: &HashMap<String, AppUpdatingStatus>, popup_area: Rect, buf: &mut Buffer) {
-                    Clear.render(popup_area, buf);
+                    let theme = get_theme();
+                    clear_area(popup_area, buf);
                     let mut lines_vector: Vec<Line> = vec![];
                     for (_, app) in update_apps_status_hashmap {
                         lines_vector.push(Line::styled(format!(" {}", app.get_status_string()), app.get_status_render_colour()));
@@ -648,13 +1567,13 @@ impl Widget for &mut App {
 
                     let bad_popup = Paragraph::new(text)
                         .wrap(Wrap { trim: true })
-                        .style(Style::new().yellow())
+                        .style(Style::new().fg(theme.warning))
                         .block(
                             Block::new()
                                 .title("Updating apps")
-                                .title_style(Style::new().white().bold())
+                                .title_style(Style::new().fg(theme.text).bold())
                                 .borders(Borders::ALL)
-                                .border_style(Style::new().red()),
+                                .border_style(Style::new().fg(theme.error)),
                         );
                     let x = Widget::render(bad_popup, popup_area, buf);
                     // sleep_ms(500);
@@ -667,32 +1586,117 @@ impl Widget for &mut App {
                     width: area.width / 2,
                     height: area.height / 3,
                 };
-                Clear.render(popup_area, buf);
+                clear_area(popup_area, buf);
 
                 render_update_status(&self.update_apps_status_hashmap, popup_area, buf);
                 // sleep_ms(100);
             }
-            _ => {} // Pass
+            SubMenus::None | SubMenus::UpdateAllCompleted => {} // Pass
+        }
+
+        if self.show_help {
+            render_help_popup(self.selected_tab, &self.keymap, area, buf);
         }
+        if let Some(dialog) = &self.dialog {
+            self.clickable_areas.dialog_buttons = render_dialog(dialog, area, buf);
+        }
+
+        // Errors are rendered on top of any other popup
+        if let Some(error_message) = &self.error_popup {
+            self.clickable_areas.popup_close_button = Some(render_error_popup(error_message, area, buf));
+        }
+    }
+}
+
+// Clears the area for a popup or pane, keeping the colors of the theme.
+fn clear_area(area: Rect, buf: &mut Buffer) {
+    let theme = get_theme();
+    Clear.render(area, buf);
+    buf.set_style(area, Style::new().fg(theme.text).bg(theme.background));
+}
+
+// Line with the search and filters on top of the list, if any is set.
+// Returns the area left for the list.
+fn render_list_filter(area: Rect, buf: &mut Buffer, list_filter: &ListFilter) -> Rect {
+    let theme = get_theme();
+    if !list_filter.is_active() {
+        return area;
+    }
+    let [filter_area, list_area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    Line::styled(list_filter.get_description(), theme.warning).render(filter_area, buf);
+    list_area
+}
+
+// Returns the area of the close button.
+fn render_error_popup(error_message: &str, area: Rect, buf: &mut Buffer) -> Rect {
+    let theme = get_theme();
+    // take up a third of the screen vertically and half horizontally
+    let popup_area = Rect {
+        x: area.x + area.width / 4,
+        y: area.y + area.height / 3,
+        width: area.width / 2,
+        height: area.height / 3,
+    };
+    clear_area(popup_area, buf);
+
+    let mut lines_vector: Vec<Line> = vec![];
+    for line in error_message.lines() {
+        lines_vector.push(Line::styled(line.to_string(), theme.error));
     }
+    // Room for the close button
+    lines_vector.push(Line::raw(""));
+
+    let error_popup = Paragraph::new(Text::from(lines_vector))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::new()
+                .title("Error")
+                .title_style(Style::new().fg(theme.text).bold())
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(theme.error)),
+        );
+    Widget::render(error_popup, popup_area, buf);
+
+    let close_button = Line::styled("[ Close (Enter/Esc) ]", Style::new().fg(theme.text).bold());
+    let inner_area = popup_area.inner(Margin::new(1, 1));
+    let close_button_width = (close_button.width() as u16).min(inner_area.width);
+    let close_button_area = Rect {
+        x: inner_area.x + (inner_area.width - close_button_width) / 2,
+        y: inner_area.bottom().saturating_sub(1),
+        width: close_button_width,
+        height: 1,
+    };
+    close_button.render(close_button_area, buf);
+    close_button_area
+}
+
+// Same layout as render_tabs: the titles one after the other, separated by a space.
+fn get_tab_areas(tabs_area: Rect) -> Vec<(Rect, SelectedTab)> {
+    let mut x = tabs_area.x;
+    let mut tab_areas: Vec<(Rect, SelectedTab)> = vec![];
+    for tab in SelectedTab::iter() {
+        let title_width = tab.title().width() as u16;
+        tab_areas.push((Rect::new(x, tabs_area.y, title_width, 1).intersection(tabs_area), tab));
+        x = x.saturating_add(title_width + 1);
+    }
+    tab_areas
 }
 
 
 impl SelectedTab {
 
-    fn render_enable_mods_tab(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage) {
+    fn render_enable_mods_tab(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, app_names: &[String]) {
+        let theme = get_theme();
 
-        let mut c=0;
         let mut styled_lines: Vec<ListItem> = vec![];
-        for app_name in tab_storage.get_app_names() {
-            let color = alternate_colors(c);
-            c+=1;
+        for (index, app_name) in app_names.iter().enumerate() {
+            let color = alternate_colors(index);
 
-            let app= tab_storage.config_manager.config.apps.get(&app_name).unwrap();
+            let app= tab_storage.config_manager.config.apps.get(app_name).unwrap();
 
             let line: Line = match app.enabled {
-                true => Line::styled(format!(" ✓ {}", app.get_app_name()), COMPLETED_TEXT_FG_COLOR),
-                false => Line::styled(format!(" ☐ {}", app.get_app_name()), TEXT_FG_COLOR)
+                true => Line::styled(format!(" {} ✓ {}", tab_storage.get_mark(app_name), app.get_app_name()), theme.success),
+                false => Line::styled(format!(" {} ☐ {}", tab_storage.get_mark(app_name), app.get_app_name()), theme.text)
             };
 
             styled_lines.push(ListItem::new(line).bg(color));
@@ -706,27 +1710,31 @@ impl SelectedTab {
         StatefulWidget::render(list, area, buffer, &mut tab_storage.list_state);
     }
 
-    fn render_update_mods_tab(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, latest_tags_pulled_map: &mut HashMap<String,TagInfo>) {
+    fn render_update_mods_tab(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, app_names: &[String], latest_tags_pulled_map: &mut HashMap<String,CachedRelease>) {
+        let theme = get_theme();
 
         let mut c=0;
         let mut styled_lines: Vec<ListItem> = vec![];
-        for app_name in tab_storage.get_enabled_app_names() {
+        for app_name in app_names {
             let color = alternate_colors(c);
 
-            let app= tab_storage.config_manager.config.apps.get(&app_name).unwrap();
+            let app= tab_storage.config_manager.config.apps.get(app_name).unwrap();
             if app.enabled {
 
                 // Latest patch downloaded
-                let line: Line = match latest_tags_pulled_map.get(&app.get_app_name()) {
-                    None => {Line::styled(format!(" ? {}", app.get_app_name()), GREY_TEXT_FG_COLOR)}  // Need to fetch updates
+                let mut line: Line = match latest_tags_pulled_map.get(&app.get_app_name()) {
+                    _ if app.is_local_install() => {Line::styled(format!(" L {} (local build, not updated)", app.get_app_name()), theme.muted)}
+                    None => {Line::styled(format!(" ? {}", app.get_app_name()), theme.muted)}  // Need to fetch updates
                     Some(value) => {
-                        // Differs with latest pulled
-                        match app.tag_name == value.tag_name && !app.tag_name.is_empty() {
-                            true => {Line::styled(format!(" ✓ {}", app.get_app_name()), COMPLETED_TEXT_FG_COLOR)} // Up to date
-                            false => {Line::styled(format!(" ! {}", app.get_app_name()), YELLOW_TEXT_FG_COLOR)} // "New" version found
+                        // Compared with latest pulled
+                        match app.compare_version(&value.tag_info) {
+                            VersionComparison::Same => {Line::styled(format!(" ✓ {}", app.get_app_name()), theme.success)} // Up to date
+                            VersionComparison::Older => {Line::styled(format!(" ✓ {} (newer than the latest release)", app.get_app_name()), theme.muted)} // Pinned or downgraded on GitHub
+                            VersionComparison::Newer => {Line::styled(format!(" ! {}", app.get_app_name()), theme.warning)} // New version found
                         }
                     }
                 };
+                line.spans.insert(0, Span::raw(format!(" {}", tab_storage.get_mark(app_name))));
                 c+=1;
 
                 styled_lines.push(ListItem::new(line).bg(color));
@@ -739,6 +1747,97 @@ impl SelectedTab {
 
         StatefulWidget::render(list, area, buffer, &mut tab_storage.list_state);
     }
+
+    fn render_game_folder_settings(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage, game_folder_input: &Option<String>) {
+        let theme = get_theme();
+        let xrd_game_folder = tab_storage.config_manager.config.xrd_game_folder.to_string();
+        let mut text_lines: Vec<Line> = vec![];
+
+        match game_folder_input {
+            Some(input) => {
+                text_lines.push(Line::styled(format!("New game folder: {input}_"), theme.warning));
+                text_lines.push(Line::styled("Enter to save | Esc to cancel | Leave it empty to detect it automatically".to_string(), theme.muted));
+            }
+            None => {
+                match xrd_game_folder.is_empty() {
+                    true => {
+                        text_lines.push(Line::styled("Game folder: not set".to_string(), theme.text));
+                        text_lines.push(Line::styled(" ? It will be detected automatically from the Steam libraries".to_string(), theme.muted));
+                    }
+                    false => {
+                        text_lines.push(Line::styled(format!("Game folder: '{xrd_game_folder}'"), theme.text));
+                        match validate_xrd_game_folder(&xrd_game_folder) {
+                            Ok(_) => text_lines.push(Line::styled(" ✓ GuiltyGearXrd.exe found".to_string(), theme.success)),
+                            Err(e) => text_lines.push(Line::styled(format!(" ✗ {e}"), theme.error)),
+                        }
+                    }
+                }
+            }
+        }
+
+        let active_profile = &tab_storage.config_manager.config.active_profile;
+        let profile_count = tab_storage.config_manager.config.profiles.len();
+        text_lines.push(Line::styled(format!("Profile: '{active_profile}' ({profile_count} saved)"), theme.text));
+        match tab_storage.config_manager.config.offline_mode {
+            true => text_lines.push(Line::styled("Offline mode: on, releases are only taken from the cache".to_string(), theme.warning)),
+            false => text_lines.push(Line::styled("Offline mode: off".to_string(), theme.text)),
+        }
+        let theme_name = &tab_storage.config_manager.config.theme;
+        text_lines.push(Line::styled(format!("Theme: '{}'{}", if theme_name.is_empty() { DEFAULT_THEME_NAME } else { theme_name }, if is_no_color_set() { " (colors disabled by NO_COLOR)" } else { "" }), theme.text));
+
+        let paragraph = Paragraph::new(Text::from(text_lines))
+            .block(Block::bordered().fg(theme.border).title("Game folder".bold()))
+            .wrap(Wrap { trim: false });
+        Widget::render(paragraph, area, buffer);
+    }
+
+    fn render_steam_libraries(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage) {
+        let theme = get_theme();
+
+        let mut styled_lines: Vec<ListItem> = vec![];
+        for (index, library) in tab_storage.steam_libraries.iter().enumerate() {
+            let color = alternate_colors(index);
+
+            let line: Line = match library.has_xrd {
+                true => Line::styled(format!(" ✓ {} -> '{}'", library.path, library.get_xrd_game_folder()), theme.success),
+                false => Line::styled(format!(" ☐ {} (Xrd not installed)", library.path), theme.muted)
+            };
+
+            styled_lines.push(ListItem::new(line).bg(color));
+        }
+
+        let list = List::new(styled_lines)
+            .block(Block::bordered().fg(theme.border).title("Detected Steam libraries".bold()))
+            .highlight_symbol(">")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buffer, &mut tab_storage.list_state);
+    }
+
+    fn render_patch_mods_tab(self, area: Rect, buffer: &mut Buffer, tab_storage: &mut TabStorage) {
+        let theme = get_theme();
+
+        let mut styled_lines: Vec<ListItem> = vec![];
+        for (index, app_name) in tab_storage.get_enabled_app_names().into_iter().enumerate() {
+            let color = alternate_colors(index);
+
+            let app= tab_storage.config_manager.config.apps.get(&app_name).unwrap();
+
+            let line: Line = match (app.is_patchable(), app.patched) {
+                (false, _) => Line::styled(format!(" {} - {}", tab_storage.get_mark(&app_name), app.get_app_name()), theme.muted), // Nothing to patch
+                (true, true) => Line::styled(format!(" {} ✓ {}", tab_storage.get_mark(&app_name), app.get_app_name()), theme.success),
+                (true, false) => Line::styled(format!(" {} ☐ {}", tab_storage.get_mark(&app_name), app.get_app_name()), theme.text)
+            };
+
+            styled_lines.push(ListItem::new(line).bg(color));
+        }
+
+        let list = List::new(styled_lines)
+            .highlight_symbol(">")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buffer, &mut tab_storage.list_state);
+    }
 }
 
 impl SelectedTab {
@@ -757,51 +1856,123 @@ impl SelectedTab {
     }
 }
 
-fn render_title(area: Rect, buf: &mut Buffer) {
-    "Ratatui Tabs Example".bold().render(area, buf);
+fn render_title(area: Rect, buf: &mut Buffer, unsaved_changes: bool) {
+    match unsaved_changes {
+        true => "* Unsaved changes".bold().fg(get_theme().warning).render(area, buf),
+        false => "Ratatui Tabs Example".bold().render(area, buf),
+    }
 }
 
 fn render_footer(app: &App, area: Rect, buf: &mut Buffer) {
-    match app.selected_tab {
-        SelectedTab::Tab1 => {
-            Line::raw("Use ← ↓ ↑ → to navigate | Enter to Select/Deselect | S/s to save | R/r to reload config | Q/q to quit")
-            // Line::raw("Use ◄ ▲ ▼ ► to navigate | Enter to Select/Deselect | S/s to save | R/r to reload config | Q/q to quit")
-            // Line::raw("Use ↓↑ to move | ◄ ► to change tab | Enter to Select/Deselect | S/s to save | R/r to reload config | Q/q to quit")
-                .centered()
-                .render(area, buf);
-        }
-        SelectedTab::Tab2 => {
-            // | Enter to Update Selected
-            Line::raw("Use ← ↓ ↑ → to navigate | s/S Search Updates | u/U to update All | R/r to reload config | Q/q to quit")
-            // Line::raw("Use ◄ ▲ ▼ ► to navigate | s/S Search Updates | u/U to update All | R/r to reload config | Q/q to quit")
-            // Line::raw("Use ↓↑ to move | ◄ ► to change tab | s/S Search Updates | u/U to update All | R/r to reload config | Q/q to quit")
-                .centered()
-                .render(area, buf);
-        }
-        // SelectedTab::Tab2 => {}
-        // SelectedTab::Tab3 => {}
-        // SelectedTab::Tab4 => {}
-        _ => {
-            Line::raw("◄ ► to change tab | Press q to quit")
-                .centered()
-                .render(area, buf);
-        }
-    }
-}
-
-const fn alternate_colors(i: usize) -> Color {
+    let footer = app.selected_tab.get_footer_actions().iter()
+        .map(|action| format!("{} {}", app.keymap.describe_keys(*action), action.get_description()))
+        .collect::<Vec<String>>()
+        .join(" | ");
+    Line::raw(footer)
+        .centered()
+        .render(area, buf);
+}
+
+// Returns the area of each button.
+fn render_dialog(dialog: &Dialog, area: Rect, buf: &mut Buffer) -> Vec<Rect> {
+    let theme = get_theme();
+    let popup_width = (area.width / 2).max(60).min(area.width);
+    // Wrapped summary, blank line, buttons and borders
+    let line_width = popup_width.saturating_sub(2).max(1) as usize;
+    let summary_height: usize = dialog.summary.iter().map(|line| line.chars().count().div_ceil(line_width).max(1)).sum();
+    let popup_height = (summary_height as u16 + 4).min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width - popup_width) / 2,
+        y: area.y + (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+    clear_area(popup_area, buf);
+
+    let lines_vector: Vec<Line> = dialog.summary.iter().map(|line| Line::styled(line.to_string(), theme.text)).collect();
+    let dialog_popup = Paragraph::new(Text::from(lines_vector))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::new()
+                .title(dialog.title.to_string())
+                .title_style(Style::new().fg(theme.text).bold())
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(theme.warning)),
+        );
+    Widget::render(dialog_popup, popup_area, buf);
+
+    // "[ Yes (y) ]  [ No (n/Esc) ]" centered on the last line, the selected one reversed.
+    let inner_area = popup_area.inner(Margin::new(1, 1));
+    let button_labels: Vec<String> = dialog.buttons.iter().map(|button| format!("[ {} ]", button.label)).collect();
+    let buttons_width = button_labels.iter().map(|label| label.chars().count() as u16 + 2).sum::<u16>().saturating_sub(2);
+    let mut x = inner_area.x + inner_area.width.saturating_sub(buttons_width) / 2;
+    let y = inner_area.bottom().saturating_sub(1);
+    let mut button_areas: Vec<Rect> = vec![];
+    for (index, label) in button_labels.iter().enumerate() {
+        let button_area = Rect::new(x, y, label.chars().count() as u16, 1).intersection(inner_area);
+        let button_style = match index == dialog.selected_button {
+            true => Style::new().fg(theme.text).bold().add_modifier(Modifier::REVERSED),
+            false => Style::new().fg(theme.text),
+        };
+        Line::styled(label.to_string(), button_style).render(button_area, buf);
+        button_areas.push(button_area);
+        x = x.saturating_add(button_area.width + 2);
+    }
+    button_areas
+}
+
+// Every key of the tab, with the action it runs.
+fn render_help_popup(selected_tab: SelectedTab, keymap: &Keymap, area: Rect, buf: &mut Buffer) {
+    let theme = get_theme();
+    let actions = selected_tab.get_actions();
+    let keys: Vec<String> = actions.iter().map(|action| keymap.describe_keys(*action)).collect();
+    let keys_width = keys.iter().map(|keys| keys.chars().count()).max().unwrap_or_default();
+
+    let popup_width = (area.width / 2).max(60).min(area.width);
+    let popup_height = (actions.len() as u16 + 4).min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width - popup_width) / 2,
+        y: area.y + (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+    clear_area(popup_area, buf);
+
+    let mut lines_vector: Vec<Line> = vec![];
+    for (action, keys) in actions.iter().zip(keys) {
+        lines_vector.push(Line::from(vec![
+            Span::styled(format!(" {keys:<keys_width$}  "), Style::new().fg(theme.warning).bold()),
+            Span::styled(action.get_description(), theme.text),
+        ]));
+    }
+    lines_vector.push(Line::raw(""));
+    lines_vector.push(Line::styled(" Press any key to close", theme.muted).italic());
+
+    let help_popup = Paragraph::new(Text::from(lines_vector))
+        .block(
+            Block::new()
+                .title(format!("Keys of '{selected_tab}'"))
+                .title_style(Style::new().fg(theme.text).bold())
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(theme.warning)),
+        );
+    Widget::render(help_popup, popup_area, buf);
+}
+
+fn alternate_colors(i: usize) -> Color {
+    let theme = get_theme();
     if i % 2 == 0 {
-        NORMAL_ROW_BG
+        theme.row_bg
     } else {
-        ALT_ROW_BG_COLOR
+        theme.alt_row_bg
     }
 }
 
 // impl From<&AppStruct> for ListItem<'_> {
 //     fn from(app: &AppStruct) -> Self {
 //         let line = match app.enabled {
-//             true => Line::styled(format!(" ✓ {}", app.get_app_name()), COMPLETED_TEXT_FG_COLOR),
-//             false => Line::styled(format!(" ☐ {}", app.get_app_name()), TEXT_FG_COLOR)
+//             true => Line::styled(format!(" ✓ {}", app.get_app_name()), theme.success),
+//             false => Line::styled(format!(" ☐ {}", app.get_app_name()), theme.text)
 //         };
 //         ListItem::new(line)
 //     }
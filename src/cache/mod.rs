@@ -0,0 +1,146 @@
+// Cache of the releases fetched from GitHub and of their assets, so mods can be (re)installed without internet.
+//
+//   <cache_dir>/<owner>/<repo>/latest.json                        latest release, as of the last check
+//   <cache_dir>/<owner>/<repo>/releases.json                      most recent releases, as of the last check
+//   <cache_dir>/<owner>/<repo>/tags/<tag_name>.json                every release fetched
+//   <cache_dir>/<owner>/<repo>/assets/<release id>/<asset name>    downloaded assets, never overwritten
+
+use std::fs;
+use std::fs::create_dir_all;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::error::{XrdError, XrdResult};
+use crate::functions::{download_file_to_path, read_optional_file, write_file_atomically};
+use crate::stuff::{TagAssets, TagInfo};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedRelease {
+    // RFC 3339
+    pub(crate) fetched_at: String,
+    pub(crate) tag_info: TagInfo,
+}
+
+impl CachedRelease {
+    pub(crate) fn new(tag_info: TagInfo) -> CachedRelease {
+        CachedRelease {
+            fetched_at: Utc::now().to_rfc3339(),
+            tag_info,
+        }
+    }
+
+    // "just now", "5 minutes ago", "3 days ago"...
+    pub(crate) fn get_formated_age(&self) -> String {
+        let Ok(fetched_at) = DateTime::parse_from_rfc3339(&self.fetched_at) else {
+            return "unknown age".to_string();
+        };
+        let age = Utc::now().signed_duration_since(fetched_at);
+
+        let plural = |count: i64| if count == 1 { "" } else { "s" };
+        match (age.num_days(), age.num_hours(), age.num_minutes()) {
+            (days, _, _) if days > 0 => format!("{days} day{} ago", plural(days)),
+            (_, hours, _) if hours > 0 => format!("{hours} hour{} ago", plural(hours)),
+            (_, _, minutes) if minutes > 0 => format!("{minutes} minute{} ago", plural(minutes)),
+            _ => "just now".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedReleaseList {
+    // RFC 3339
+    pub(crate) fetched_at: String,
+    pub(crate) releases: Vec<TagInfo>,
+}
+
+// Cache of a single app.
+pub struct ReleaseCache {
+    app_name: String,
+    repo_cache_dir: String,
+    // Assets missing from the cache are an error instead of being downloaded.
+    offline: bool,
+}
+
+impl ReleaseCache {
+    pub(crate) fn new(cache_dir: &String, app_name: &String, offline: bool) -> ReleaseCache {
+        ReleaseCache {
+            app_name: app_name.to_string(),
+            repo_cache_dir: format!("{}/{}", cache_dir, app_name),
+            offline,
+        }
+    }
+
+    fn get_release_file_path(&self, tag_name: &str) -> String {
+        // Tags may contain slashes
+        format!("{}/tags/{}.json", self.repo_cache_dir, tag_name.replace(['/', '\\'], "_"))
+    }
+
+    fn read_release(release_file_path: &String) -> XrdResult<Option<CachedRelease>> {
+        match read_optional_file(release_file_path)? {
+            Some(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn write_release(release_file_path: &String, cached_release: &CachedRelease) -> XrdResult<()> {
+        if let Some(release_dir) = Path::new(release_file_path).parent() {
+            create_dir_all(release_dir)?;
+        }
+        write_file_atomically(release_file_path, serde_json::to_string_pretty(cached_release)?.as_bytes())
+    }
+
+    pub(crate) fn get_latest_release(&self) -> XrdResult<CachedRelease> {
+        Self::read_release(&format!("{}/latest.json", self.repo_cache_dir))?
+            .ok_or(XrdError::NotCached(format!("latest release of '{}'", self.app_name)))
+    }
+
+    pub(crate) fn get_release(&self, tag_name: &String) -> XrdResult<CachedRelease> {
+        Self::read_release(&self.get_release_file_path(tag_name))?
+            .ok_or(XrdError::NotCached(format!("release '{}' of '{}'", tag_name, self.app_name)))
+    }
+
+    pub(crate) fn get_releases(&self) -> XrdResult<CachedReleaseList> {
+        match read_optional_file(&format!("{}/releases.json", self.repo_cache_dir))? {
+            Some(contents) => Ok(serde_json::from_str(&contents)?),
+            None => Err(XrdError::NotCached(format!("list of releases of '{}'", self.app_name))),
+        }
+    }
+
+    pub(crate) fn store_releases(&self, releases: Vec<TagInfo>) -> XrdResult<CachedReleaseList> {
+        let cached_release_list = CachedReleaseList {
+            fetched_at: Utc::now().to_rfc3339(),
+            releases,
+        };
+        create_dir_all(&self.repo_cache_dir)?;
+        write_file_atomically(&format!("{}/releases.json", self.repo_cache_dir), serde_json::to_string_pretty(&cached_release_list)?.as_bytes())?;
+        Ok(cached_release_list)
+    }
+
+    pub(crate) fn store_release(&self, tag_info: TagInfo, is_latest: bool) -> XrdResult<CachedRelease> {
+        let cached_release = CachedRelease::new(tag_info);
+        Self::write_release(&self.get_release_file_path(&cached_release.tag_info.tag_name), &cached_release)?;
+        if is_latest {
+            Self::write_release(&format!("{}/latest.json", self.repo_cache_dir), &cached_release)?;
+        }
+        Ok(cached_release)
+    }
+
+    // Path of the cached asset, downloading it first if missing.
+    pub(crate) fn get_asset(&self, tag_info: &TagInfo, asset: &TagAssets) -> XrdResult<String> {
+        let assets_dir = format!("{}/assets/{}", self.repo_cache_dir, tag_info.id);
+        let asset_file_path = format!("{}/{}", assets_dir, asset.name);
+
+        if !Path::new(&asset_file_path).is_file() {
+            if self.offline {
+                return Err(XrdError::NotCached(format!("asset '{}' of the release '{}' of '{}'", asset.name, tag_info.tag_name, self.app_name)));
+            }
+            // Downloaded apart, so an interrupted download isn't taken as cached.
+            let partial_dir = format!("{}/.partial", assets_dir);
+            create_dir_all(&partial_dir)?;
+            // The downloaded file is named after the URL, which can be encoded differently than the asset name.
+            let downloaded_file_path = download_file_to_path(asset.browser_download_url.to_string(), partial_dir.to_string())?;
+            fs::rename(downloaded_file_path, &asset_file_path)?;
+        }
+        Ok(asset_file_path)
+    }
+}
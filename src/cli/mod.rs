@@ -0,0 +1,322 @@
+// Command line commands, the TUI is launched when no command is given.
+
+use std::time::Duration;
+use crate::error::{XrdError, XrdResult};
+use crate::functions::{get_detected_steam_libraries, get_xrd_executable_path};
+use crate::lockfile::{read_lockfile, write_lockfile, DEFAULT_LOCKFILE_NAME};
+use crate::manager::Manager;
+use crate::watch::{run_update_check, watch, DEFAULT_WATCH_INTERVAL_MINUTES};
+use crate::storage::{get_storage_paths, PORTABLE_MARKER_FILE_NAME};
+
+const HELP_MESSAGE: &str = "Usage: xrd_mods_downloader [COMMAND]
+
+Without a command the TUI is launched.
+
+Commands:
+  set-game-folder <PATH>   Validate and store the game folder. An empty path resets it to be detected automatically.
+  list-steam-libraries     List the detected Steam libraries and whether Xrd is installed in them.
+  profile list             List the profiles, the active one is marked.
+  profile save <NAME>      Store the current setup (enabled and patched mods) as a profile, and make it active.
+  profile switch <NAME>    Enable the profile's mods, and apply or revert patches to match it.
+  profile delete <NAME>    Delete a profile.
+  lockfile export [PATH]   Write the exact versions of the enabled mods to a lockfile (default: xrd_mods.lock.json).
+  lockfile import [PATH]   Install the exact versions listed in a lockfile, and enable only its mods.
+  offline <on|off>         Only use the releases and assets cached, for machines without internet.
+  reinstall <OWNER/REPO>   Download the installed version of a mod again, from the cache if available.
+  install-file <OWNER/REPO> <PATH>
+                           Install a mod from a local zip, executable or folder. It won't be updated until reinstalled.
+  watch [--once] [--interval <MINUTES>] [--no-notify]
+                           Check for new versions of the enabled mods every hour (or the given interval), writing the
+                           result to update_status.json and sending a desktop notification for the new ones.
+  cache                    List the latest releases cached, and when they were checked.
+  paths                    Print where the settings, state, downloaded mods and cache are stored.
+  help                     Print this message.";
+
+// Returns false when no command was given.
+pub fn run_command(args: &[String]) -> XrdResult<bool> {
+    let command = match args.first() {
+        Some(command) => command.as_str(),
+        None => return Ok(false),
+    };
+
+    match command {
+        "set-game-folder" => {
+            let xrd_game_folder = args.get(1).ok_or(XrdError::Other("Missing the game folder path. Usage: set-game-folder <PATH>".to_string()))?;
+            set_game_folder(xrd_game_folder.to_string())?;
+        }
+        "list-steam-libraries" => list_steam_libraries()?,
+        "paths" => print_paths()?,
+        "profile" => {
+            match (args.get(1).map(|subcommand| subcommand.as_str()), args.get(2)) {
+                (Some("list"), _) => list_profiles()?,
+                (Some("save"), Some(profile_name)) => save_profile(profile_name.to_string())?,
+                (Some("switch"), Some(profile_name)) => switch_profile(profile_name.to_string())?,
+                (Some("delete"), Some(profile_name)) => delete_profile(profile_name.to_string())?,
+                _ => return Err(XrdError::Other("Usage: profile list | profile save <NAME> | profile switch <NAME> | profile delete <NAME>".to_string())),
+            }
+        }
+        "lockfile" => {
+            let lockfile_path = args.get(2).map_or(DEFAULT_LOCKFILE_NAME.to_string(), |lockfile_path| lockfile_path.to_string());
+            match args.get(1).map(|subcommand| subcommand.as_str()) {
+                Some("export") => export_lockfile(lockfile_path)?,
+                Some("import") => import_lockfile(lockfile_path)?,
+                _ => return Err(XrdError::Other("Usage: lockfile export [PATH] | lockfile import [PATH]".to_string())),
+            }
+        }
+        "offline" => {
+            let offline_mode = match args.get(1).map(|value| value.as_str()) {
+                Some("on") => true,
+                Some("off") => false,
+                _ => return Err(XrdError::Other("Usage: offline <on|off>".to_string())),
+            };
+            set_offline_mode(offline_mode)?;
+        }
+        "reinstall" => {
+            let app_name = args.get(1).ok_or(XrdError::Other("Missing the mod name. Usage: reinstall <OWNER/REPO>".to_string()))?;
+            reinstall_app(app_name.to_string())?;
+        }
+        "install-file" => {
+            let (Some(app_name), Some(source_path)) = (args.get(1), args.get(2)) else {
+                return Err(XrdError::Other("Usage: install-file <OWNER/REPO> <PATH>".to_string()));
+            };
+            install_local_app(app_name.to_string(), source_path.to_string())?;
+        }
+        "watch" => {
+            let mut interval_minutes = DEFAULT_WATCH_INTERVAL_MINUTES;
+            let mut once = false;
+            let mut send_notification = true;
+            let mut options = args.iter().skip(1);
+            while let Some(option) = options.next() {
+                match option.as_str() {
+                    "--once" => once = true,
+                    "--no-notify" => send_notification = false,
+                    "--interval" => {
+                        // Too large to be counted in seconds is rejected too.
+                        interval_minutes = options.next().and_then(|minutes| minutes.parse::<u64>().ok()).filter(|minutes| *minutes > 0 && minutes.checked_mul(60).is_some())
+                            .ok_or(XrdError::Other("--interval expects a number of minutes greater than 0".to_string()))?;
+                    }
+                    unknown_option => return Err(XrdError::Other(format!("Unknown option '{unknown_option}'. Usage: watch [--once] [--interval <MINUTES>] [--no-notify]"))),
+                }
+            }
+            match once {
+                true => { run_update_check(send_notification)?; }
+                false => {
+                    println!("Checking for updates every {interval_minutes} minutes, press Ctrl+C to stop.");
+                    watch(Duration::from_secs(interval_minutes * 60), send_notification)?;
+                }
+            }
+        }
+        "cache" => list_cache()?,
+        "help" | "--help" | "-h" => println!("{HELP_MESSAGE}"),
+        unknown_command => {
+            return Err(XrdError::Other(format!("Unknown command '{unknown_command}'.\n\n{HELP_MESSAGE}")));
+        }
+    }
+    Ok(true)
+}
+
+fn set_game_folder(xrd_game_folder: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    manager.config.set_xrd_game_folder(xrd_game_folder)?;
+    manager.save_config()?;
+
+    match manager.config.xrd_game_folder.is_empty() {
+        true => println!("Game folder reset, it will be detected automatically."),
+        false => println!("Game folder set to '{}'.", manager.config.xrd_game_folder),
+    }
+    Ok(())
+}
+
+fn list_steam_libraries() -> XrdResult<()> {
+    let libraries = get_detected_steam_libraries()?;
+
+    if libraries.is_empty() {
+        println!("No Steam libraries found.");
+    }
+
+    for library in libraries {
+        match library.has_xrd {
+            true => {
+                let xrd_game_folder = library.get_xrd_game_folder();
+                let found_executable = std::path::Path::new(&get_xrd_executable_path(&xrd_game_folder)).is_file();
+                println!("[✅ ] {} -> '{}'{}", library.path, xrd_game_folder, if found_executable { "" } else { " (GuiltyGearXrd.exe missing)" });
+            }
+            false => println!("[  ] {}", library.path),
+        }
+    }
+    Ok(())
+}
+
+fn print_paths() -> XrdResult<()> {
+    let storage_paths = get_storage_paths()?;
+
+    match storage_paths.portable {
+        true => {
+            println!("Portable mode (XRD_MOD_FOLDER set, or a '{PORTABLE_MARKER_FILE_NAME}' file or db.json next to the executable)");
+            println!("Database: '{}'", storage_paths.settings_file);
+        }
+        false => {
+            println!("Settings: '{}'", storage_paths.settings_file);
+            println!("State: '{}'", storage_paths.state_file);
+        }
+    }
+    println!("Downloaded mods: '{}'", storage_paths.mods_dir);
+    println!("Cache: '{}'", storage_paths.cache_dir);
+    Ok(())
+}
+
+fn list_profiles() -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+
+    for profile_name in manager.config.get_profile_names() {
+        let profile = &manager.config.profiles[&profile_name];
+        let marker = if profile_name == manager.config.active_profile { "*" } else { " " };
+        println!("{marker} {profile_name}");
+        println!("    Enabled: {}", profile.enabled_apps.join(", "));
+        println!("    Patched: {}", profile.patched_apps.join(", "));
+    }
+    Ok(())
+}
+
+fn save_profile(profile_name: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    manager.save_profile(profile_name.to_string());
+    manager.save_config()?;
+    println!("Profile '{profile_name}' saved.");
+    Ok(())
+}
+
+fn switch_profile(profile_name: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    let switch_result = manager.switch_profile(profile_name.to_string());
+    // Whatever was applied before an error is saved.
+    manager.save_config()?;
+
+    for (app_name, patch_result) in switch_result? {
+        match patch_result {
+            Ok(patch_output) if patch_output.is_successful() => println!("[✅ ] Patched '{app_name}'"),
+            Ok(patch_output) => println!("[⚠️ ] Error patching '{app_name}': {}\nLog: '{}'", patch_output.get_exit_code_explanation(), patch_output.log_file_path),
+            Err(e) => println!("[⚠️ ] Error patching '{app_name}': {e}"),
+        }
+    }
+    println!("Switched to profile '{profile_name}'.");
+    Ok(())
+}
+
+fn delete_profile(profile_name: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    manager.delete_profile(&profile_name)?;
+    manager.save_config()?;
+    println!("Profile '{profile_name}' deleted.");
+    Ok(())
+}
+
+fn export_lockfile(lockfile_path: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    let lockfile = manager.export_lockfile()?;
+    write_lockfile(&lockfile_path, &lockfile)?;
+
+    for app_name in manager.get_enabled_app_names() {
+        if manager.config.apps[&app_name].is_local_install() {
+            println!("[⚠️ ] '{app_name}' was installed from a local file and isn't included.");
+        }
+    }
+
+    for (app_name, locked_app) in &lockfile.apps {
+        println!("{app_name} {} ({} assets)", locked_app.tag_name, locked_app.asset_hashes.len());
+    }
+    println!("Lockfile written to '{lockfile_path}'.");
+    Ok(())
+}
+
+fn import_lockfile(lockfile_path: String) -> XrdResult<()> {
+    let lockfile = read_lockfile(&lockfile_path)?;
+    if lockfile.tool_version != env!("CARGO_PKG_VERSION") {
+        println!("[⚠️ ] The lockfile was exported with the version '{}' of the tool, this is the version '{}'.", lockfile.tool_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    let install_results = manager.import_lockfile(&lockfile);
+    // Whatever was installed before an error is saved.
+    manager.save_config()?;
+
+    let mut failed_count = 0;
+    for (app_name, install_result) in install_results {
+        let tag_name = &lockfile.apps[&app_name].tag_name;
+        match install_result {
+            Ok(_) => println!("[✅ ] '{app_name}' at {tag_name}"),
+            Err(e) => {
+                failed_count += 1;
+                println!("[⚠️ ] Error installing '{app_name}' {tag_name}: {e}");
+            }
+        }
+    }
+
+    match failed_count {
+        0 => {
+            println!("Lockfile '{lockfile_path}' imported.");
+            Ok(())
+        }
+        _ => Err(XrdError::Other(format!("{failed_count} mods from the lockfile couldn't be installed"))),
+    }
+}
+
+fn set_offline_mode(offline_mode: bool) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    manager.config.offline_mode = offline_mode;
+    manager.save_config()?;
+
+    match offline_mode {
+        true => println!("Offline mode enabled, releases and assets are only taken from the cache."),
+        false => println!("Offline mode disabled."),
+    }
+    Ok(())
+}
+
+fn reinstall_app(app_name: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    manager.reinstall_app(app_name.to_string())?;
+    manager.save_config()?;
+    println!("Reinstalled '{app_name}' {}.", manager.config.apps[&app_name].tag_name);
+    Ok(())
+}
+
+fn list_cache() -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    println!("Offline mode: {}", if manager.config.offline_mode { "on" } else { "off" });
+    // Only read the cache
+    manager.config.offline_mode = true;
+
+    for app_name in manager.get_app_names() {
+        match manager.get_latest_release(&app_name) {
+            Ok(cached_release) => println!("{app_name} {} (checked {})", cached_release.tag_info.tag_name, cached_release.get_formated_age()),
+            Err(XrdError::NotCached(_)) => println!("{app_name} not cached"),
+            Err(e) => println!("{app_name} error reading the cache: {e}"),
+        }
+    }
+    Ok(())
+}
+
+fn install_local_app(app_name: String, source_path: String) -> XrdResult<()> {
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    let install_result = manager.install_local_app(app_name.to_string(), source_path.to_string());
+    // Saved even if the patch failed, the files are already installed.
+    manager.save_config()?;
+
+    if let Some(patch_output) = install_result? {
+        println!("{}", patch_output.get_formated_log());
+    }
+    println!("Installed '{app_name}' from '{}'. It won't be updated until reinstalled with 'reinstall {app_name}'.", manager.config.apps[&app_name].local_source);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// Crate-wide error type.
+// Functions return these instead of exiting or panicking, so the TUI can display them.
+
+use std::fmt::{Display, Formatter};
+use std::io;
+
+#[derive(Debug)]
+pub enum XrdError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Http(reqwest::Error),
+    Zip(zip::result::ZipError),
+    Download(downloader::Error),
+    // Non 200 status code from the GitHub API.
+    GithubStatus { url: String, status: reqwest::StatusCode },
+    // Xrd couldn't be found in the Steam libraries file.
+    GameNotFound(String),
+    SteamNotFound(String),
+    // GuiltyGearXrd.exe not found in the given folder.
+    InvalidGameFolder(String),
+    AppNotFound(String),
+    // Another instance is holding the lock of the file.
+    ConfigLocked(String),
+    // The file changed on disk since it was loaded.
+    ConfigModified(String),
+    // db.json written by a newer version of this tool.
+    ConfigTooNew { found_version: u64, supported_version: u64 },
+    PatchNotSupported(String),
+    ProfileNotFound(String),
+    // Downloaded asset differs from the one recorded in the lockfile.
+    AssetHashMismatch { asset_name: String, expected: String, found: String },
+    // The release was deleted and published again under the same tag.
+    ReleaseChanged { app_name: String, tag_name: String },
+    // Offline mode, and the release or asset was never fetched.
+    NotCached(String),
+    // No copy of the unpatched GuiltyGearXrd.exe to revert the patches.
+    GameBackupNotFound(String),
+    // GuiltyGearXrd.exe or its backup changed since the backup was made.
+    GameBackupOutdated(String),
+    // Key name in the keybindings of settings.json that isn't known.
+    InvalidKeybinding { action: String, key_name: String },
+    // Same key bound to two actions of the same tab.
+    DuplicateKeybinding { key_name: String, action: String, other_action: String },
+    // Neither a built-in theme nor a theme file.
+    ThemeNotFound(String),
+    UnsupportedPlatform,
+    Other(String),
+}
+
+pub type XrdResult<T> = Result<T, XrdError>;
+
+impl Display for XrdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            XrdError::Io(e) => write!(f, "IO error: {e}"),
+            XrdError::Json(e) => write!(f, "JSON error: {e}"),
+            XrdError::Http(e) => write!(f, "HTTP error: {e}"),
+            XrdError::Zip(e) => write!(f, "Zip error: {e}"),
+            XrdError::Download(e) => write!(f, "Download error: {e}"),
+            XrdError::GithubStatus { url, status } => write!(f, "Status code {status} when requesting '{url}'"),
+            XrdError::GameNotFound(file_path) => write!(f, "Guilty Gear Xrd wasn't found in the Steam libraries listed in '{file_path}'. Set 'xrd_game_folder' manually."),
+            XrdError::SteamNotFound(reason) => write!(f, "Steam installation not found: {reason}. Set 'xrd_game_folder' manually."),
+            XrdError::InvalidGameFolder(game_folder) => write!(f, "'{game_folder}' isn't a valid game folder, 'Binaries/Win32/GuiltyGearXrd.exe' not found in it"),
+            XrdError::ConfigLocked(file_path) => write!(f, "'{file_path}' is locked by another instance of the tool, try again later"),
+            XrdError::ConfigModified(file_path) => write!(f, "'{file_path}' was modified by another instance of the tool since it was loaded. Reload the configuration and apply the changes again"),
+            XrdError::ConfigTooNew { found_version, supported_version } => write!(f, "db.json uses the schema version {found_version}, but this version of the tool only supports up to {supported_version}. Update the tool"),
+            XrdError::AppNotFound(app_name) => write!(f, "App '{app_name}' not found"),
+            XrdError::PatchNotSupported(app_name) => write!(f, "App '{app_name}' doesn't have a patch procedure"),
+            XrdError::ProfileNotFound(profile_name) => write!(f, "Profile '{profile_name}' not found"),
+            XrdError::AssetHashMismatch { asset_name, expected, found } => write!(f, "The asset '{asset_name}' doesn't match the lockfile, expected '{expected}' but found '{found}'"),
+            XrdError::ReleaseChanged { app_name, tag_name } => write!(f, "The release '{tag_name}' of '{app_name}' was published again and doesn't match the lockfile"),
+            XrdError::GameBackupNotFound(backup_file_path) => write!(f, "No backup of the unpatched GuiltyGearXrd.exe found at '{backup_file_path}', the patches can't be reverted. Verify the integrity of the game files through Steam instead"),
+            XrdError::GameBackupOutdated(reason) => write!(f, "The backup of GuiltyGearXrd.exe can't be restored: {reason}. Verify the integrity of the game files through Steam instead"),
+            XrdError::NotCached(description) => write!(f, "Offline mode: the {description} isn't cached, disable the offline mode to download it"),
+            XrdError::InvalidKeybinding { action, key_name } => write!(f, "Unknown key '{key_name}' bound to the action '{action}' in the keybindings"),
+            XrdError::DuplicateKeybinding { key_name, action, other_action } => write!(f, "The key '{key_name}' is bound to both '{action}' and '{other_action}', which are used in the same tab"),
+            XrdError::ThemeNotFound(theme_name) => write!(f, "Theme '{theme_name}' not found, use one of {} or the path to a .json theme file", crate::theme::get_built_in_theme_names().join(", ")),
+            XrdError::UnsupportedPlatform => write!(f, "Neither Linux or Windows detected"),
+            XrdError::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for XrdError {}
+
+impl From<io::Error> for XrdError {
+    fn from(e: io::Error) -> Self {
+        XrdError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for XrdError {
+    fn from(e: serde_json::Error) -> Self {
+        XrdError::Json(e)
+    }
+}
+
+impl From<reqwest::Error> for XrdError {
+    fn from(e: reqwest::Error) -> Self {
+        XrdError::Http(e)
+    }
+}
+
+impl From<zip::result::ZipError> for XrdError {
+    fn from(e: zip::result::ZipError) -> Self {
+        XrdError::Zip(e)
+    }
+}
+
+impl From<downloader::Error> for XrdError {
+    fn from(e: downloader::Error) -> Self {
+        XrdError::Download(e)
+    }
+}
@@ -1,55 +1,268 @@
 // Functions
 
-use std::{fs, io};
+use std::{env, fs, io, thread};
 use std::fs::File;
+use std::io::{Read, Write};
 use std::path::Path;
-use std::process::exit;
-use std::time::Duration;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 use downloader::{Download,downloader::Builder};
+use sha2::{Digest, Sha256};
 use zip::ZipArchive;
 
+use crate::error::{XrdError, XrdResult};
 use crate::stuff::*;
+use crate::vdf::{parse_vdf, VdfValue};
+use crate::version::VersionComparison;
 
-pub fn get_xrd_folder_from_file (steam_vdf_file_path: String) -> std::io::Result<String>  {
-    let contents = fs::read_to_string(steam_vdf_file_path)?.replace("\t"," ");
+// Windows imports
+#[cfg(target_os = "windows")]
+// Get path from Windows registry
+use winreg::{RegKey,enums::*};
 
-    let mut xrd_line: i32=-1;
-    let xrd_game_id_txt = "\"520440\"";
+pub const XRD_STEAM_APP_ID: &str = "520440";
+// Used when the app manifest can't be read.
+pub const XRD_DEFAULT_INSTALL_DIR: &str = "GUILTY GEAR Xrd -REVELATOR-";
 
-    let mut file_lines = contents.lines();
-    let mut last_storage_path:String="".to_string();
-    let mut current_line_count = 0;
-    let mut current_line_string: String;
+// Steam library listed in libraryfolders.vdf
+#[derive(Debug, Clone)]
+pub struct SteamLibrary {
+    pub(crate) path: String,
+    pub(crate) has_xrd: bool,
+}
+
+impl SteamLibrary {
+    pub(crate) fn get_xrd_manifest_path(&self) -> String {
+        Path::new(&self.path).join("steamapps").join(format!("appmanifest_{XRD_STEAM_APP_ID}.acf")).to_string_lossy().to_string()
+    }
+
+    // Reads the install dir from the app manifest, falls back to the default folder name.
+    pub(crate) fn get_xrd_game_folder(&self) -> String {
+        let install_dir = fs::read_to_string(self.get_xrd_manifest_path()).ok()
+            .and_then(|contents| parse_vdf(&contents).ok())
+            .and_then(|manifest| manifest.get("AppState")?.get_str("installdir").map(|install_dir| install_dir.to_string()))
+            .unwrap_or(XRD_DEFAULT_INSTALL_DIR.to_string());
+
+        Path::new(&self.path).join("steamapps").join("common").join(install_dir).to_string_lossy().to_string()
+    }
+}
+
+pub fn get_steam_libraries_from_file(steam_vdf_file_path: &String) -> XrdResult<Vec<SteamLibrary>> {
+    let contents = fs::read_to_string(steam_vdf_file_path)?;
+    let vdf = parse_vdf(&contents)?;
 
-    while xrd_line < 0 && current_line_count < contents.lines().count() {
-        current_line_string = file_lines.next().unwrap().to_string();
+    let libraries_vdf = match vdf.get("libraryfolders") {
+        Some(libraries_vdf) => libraries_vdf,
+        None => return Err(XrdError::Other(format!("'{steam_vdf_file_path}' doesn't contain a 'libraryfolders' section"))),
+    };
 
-        if current_line_string.contains(xrd_game_id_txt)  {
-            xrd_line = current_line_count as i32;
+    let mut libraries: Vec<SteamLibrary> = vec![];
+    for (key, value) in libraries_vdf.entries() {
+        // Libraries are numbered, other keys such as "contentstatsid" are skipped.
+        if key.parse::<u32>().is_err() {
+            continue;
         }
 
-        if current_line_string.contains("\"path\"") && xrd_line < 0 {
-            let mut tmp_path: String = current_line_string;
-            tmp_path = tmp_path.trim().to_string(); // remove extra spaces left right
-            tmp_path = tmp_path.strip_prefix("\"path\"").unwrap().to_string(); // Remove starter "path"
-            tmp_path = tmp_path.trim().to_string(); // Trim again
-            tmp_path = tmp_path.replace("\"",""); // Remove quotes
-            last_storage_path = tmp_path;
+        let mut library = match value {
+            // Current format, an object with the path and the list of installed apps.
+            VdfValue::Object(_) => match value.get_str("path") {
+                Some(path) => SteamLibrary {
+                    path: path.to_string(),
+                    has_xrd: value.get("apps").is_some_and(|apps| apps.contains_key(XRD_STEAM_APP_ID)),
+                },
+                None => continue,
+            },
+            // Old format, only the path is listed.
+            VdfValue::String(path) => SteamLibrary { path: path.to_string(), has_xrd: false },
+        };
+
+        // The apps list might be missing or outdated, the manifest is what Steam actually uses.
+        if !library.has_xrd {
+            library.has_xrd = Path::new(&library.get_xrd_manifest_path()).is_file();
         }
+        libraries.push(library);
+    }
+
+    Ok(libraries)
+}
 
-        current_line_count +=1;
+pub fn get_xrd_folder_from_file (steam_vdf_file_path: String) -> XrdResult<String>  {
+    let libraries = get_steam_libraries_from_file(&steam_vdf_file_path)?;
+
+    match libraries.iter().find(|library| library.has_xrd) {
+        Some(library) => Ok(library.get_xrd_game_folder()),
+        None => Err(XrdError::GameNotFound(steam_vdf_file_path)),
     }
+}
+
+// Folders where Steam might be installed, in order of preference.
+pub fn get_steam_root_dirs() -> XrdResult<Vec<String>> {
+    let mut steam_root_dirs: Vec<String> = vec![];
 
-    if xrd_line < 0 {
-        println!("Xrd not found, exitting...");
-        exit(1);
+    // Custom Steam location
+    if let Ok(steam_dir) = env::var("STEAM_DIR")
+        && !steam_dir.is_empty() {
+        steam_root_dirs.push(steam_dir);
     }
 
     if cfg!(windows) {
-        Ok(format!("{}\\steamapps\\common\\GUILTY GEAR Xrd -REVELATOR-",last_storage_path))
-    } else {
-        Ok(format!("{}/steamapps/common/GUILTY GEAR Xrd -REVELATOR-",last_storage_path))
+        #[cfg(target_os = "windows")]
+        {
+            let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+            if let Ok(steam_key) = hklm.open_subkey("SOFTWARE\\Wow6432Node\\Valve\\Steam") {
+                if let Ok(steam_path) = steam_key.get_value::<String, _>("InstallPath") {
+                    steam_root_dirs.push(steam_path);
+                }
+            }
+            let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+            if let Ok(steam_key) = hkcu.open_subkey("Software\\Valve\\Steam") {
+                if let Ok(steam_path) = steam_key.get_value::<String, _>("SteamPath") {
+                    steam_root_dirs.push(steam_path);
+                }
+            }
+        }
+    }
+    else if cfg!(unix) {
+        // Without a home directory only STEAM_DIR can be checked.
+        let home_path = dirs::home_dir();
+        for relative_steam_dir in [
+            ".steam/root",
+            ".steam/steam",
+            ".local/share/Steam",
+            // Flatpak
+            ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+            ".var/app/com.valvesoftware.Steam/data/Steam",
+            // Snap
+            "snap/steam/common/.local/share/Steam",
+        ] {
+            if let Some(home_path) = &home_path {
+                steam_root_dirs.push(home_path.join(relative_steam_dir).to_string_lossy().to_string());
+            }
+        }
+    }
+    else {
+        return Err(XrdError::UnsupportedPlatform);
+    }
+
+    Ok(steam_root_dirs)
+}
+
+// libraryfolders.vdf files found in the Steam folders.
+// Folders are often symlinks between each other (~/.steam/root -> ~/.local/share/Steam), duplicates are skipped.
+pub fn get_steam_library_files() -> XrdResult<Vec<String>> {
+    let mut library_files: Vec<String> = vec![];
+    let mut seen_files: Vec<std::path::PathBuf> = vec![];
+
+    for steam_root_dir in get_steam_root_dirs()? {
+        for relative_file_path in ["config/libraryfolders.vdf", "steamapps/libraryfolders.vdf"] {
+            let file_path = Path::new(&steam_root_dir).join(relative_file_path);
+            if let Ok(canonical_file_path) = fs::canonicalize(&file_path)
+                && canonical_file_path.is_file() && !seen_files.contains(&canonical_file_path) {
+                seen_files.push(canonical_file_path);
+                library_files.push(file_path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    Ok(library_files)
+}
+
+// Steam libraries from every libraryfolders.vdf found.
+pub fn get_detected_steam_libraries() -> XrdResult<Vec<SteamLibrary>> {
+    let mut libraries: Vec<SteamLibrary> = vec![];
+    let mut seen_paths: Vec<std::path::PathBuf> = vec![];
+
+    for library_file in get_steam_library_files()? {
+        // A broken file shouldn't hide the libraries from the other ones.
+        let Ok(file_libraries) = get_steam_libraries_from_file(&library_file) else { continue };
+        for library in file_libraries {
+            let canonical_path = fs::canonicalize(&library.path).unwrap_or(Path::new(&library.path).to_path_buf());
+            if !seen_paths.contains(&canonical_path) {
+                seen_paths.push(canonical_path);
+                libraries.push(library);
+            }
+        }
+    }
+
+    Ok(libraries)
+}
+
+pub fn get_xrd_executable_path(xrd_game_folder: &String) -> String {
+    Path::new(xrd_game_folder).join("Binaries").join("Win32").join("GuiltyGearXrd.exe").to_string_lossy().to_string()
+}
+
+pub fn validate_xrd_game_folder(xrd_game_folder: &String) -> XrdResult<()> {
+    match Path::new(&get_xrd_executable_path(xrd_game_folder)).is_file() {
+        true => Ok(()),
+        false => Err(XrdError::InvalidGameFolder(xrd_game_folder.to_string())),
+    }
+}
+
+pub fn find_xrd_game_folder() -> XrdResult<String> {
+    let library_files = get_steam_library_files()?;
+
+    if library_files.is_empty() {
+        return Err(XrdError::SteamNotFound(format!("no libraryfolders.vdf found in '{}'", get_steam_root_dirs()?.join("', '"))));
+    }
+
+    // A stale library file or a half-deleted install shouldn't hide the game in a later library.
+    let mut last_error: Option<XrdError> = None;
+    for library_file in &library_files {
+        if let Ok(xrd_game_folder) = get_xrd_folder_from_file(library_file.to_string()) {
+            match validate_xrd_game_folder(&xrd_game_folder) {
+                Ok(()) => return Ok(xrd_game_folder),
+                Err(e) => last_error = Some(e),
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| XrdError::GameNotFound(library_files.join("', '"))))
+}
+
+// Time to wait for another instance to release a lock.
+pub const FILE_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Advisory lock, released when the returned file is dropped.
+// Shared locks for reading, exclusive locks for writing.
+pub fn acquire_file_lock(lock_file_path: &String, exclusive: bool) -> XrdResult<File> {
+    let lock_file = fs::OpenOptions::new().create(true).truncate(false).write(true).open(lock_file_path)?;
+    let deadline = Instant::now() + FILE_LOCK_TIMEOUT;
+
+    loop {
+        let lock_result = match exclusive {
+            true => lock_file.try_lock(),
+            false => lock_file.try_lock_shared(),
+        };
+        match lock_result {
+            Ok(()) => return Ok(lock_file),
+            Err(fs::TryLockError::WouldBlock) if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+            Err(fs::TryLockError::WouldBlock) => return Err(XrdError::ConfigLocked(lock_file_path.to_string())),
+            Err(fs::TryLockError::Error(e)) => return Err(XrdError::Io(e)),
+        }
+    }
+}
+
+// None if the file doesn't exist.
+pub fn read_optional_file(file_path: &String) -> XrdResult<Option<String>> {
+    match fs::read_to_string(file_path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(XrdError::Io(e)),
+    }
+}
+
+// Writes to a temporary file that replaces the destination once fully written.
+// A crash mid-write leaves the previous file untouched.
+pub fn write_file_atomically(file_path: &String, contents: &[u8]) -> XrdResult<()> {
+    let tmp_file_path = format!("{file_path}.tmp");
+    {
+        let mut tmp_file = File::create(&tmp_file_path)?;
+        tmp_file.write_all(contents)?;
+        tmp_file.sync_all()?;
     }
+    fs::rename(&tmp_file_path, file_path)?;
+    Ok(())
 }
 
 pub fn print_different_versions(current:&AppStruct, latest:&TagInfo) -> bool {
@@ -57,28 +270,183 @@ pub fn print_different_versions(current:&AppStruct, latest:&TagInfo) -> bool {
 
     println!("Checking updates for app: {}",current.get_app_name());
 
-    if current.tag_name == latest.tag_name && current.published_at == latest.published_at {
-        println!("[✅ ] APP {} is up to date!",current.get_app_name());
+    if current.is_local_install() {
+        println!("[🚫] APP {} was installed from '{}', skipping.",current.get_app_name(),current.local_source);
         return false
-    } else {
-        println!("[⚠️ ] APP {} has a new version detected.",current.get_app_name());
-
-        // Version
-        println!("Version:\t'{}' -> '{}'",current.tag_name,latest.tag_name);
-        // Published date
-        println!("Published date: '{}' -> '{}'",current.published_at,latest.published_at);
-        // Source URL
-        println!("Source URL: '{}'",latest.html_url);
-        // Print notes
-        println!("Version notes:\n============\n{}\n============",latest.get_formated_body());
+    }
+    match current.compare_version(latest) {
+        VersionComparison::Same => {
+            println!("[✅ ] APP {} is up to date!",current.get_app_name());
+            return false
+        }
+        VersionComparison::Older => {
+            println!("[✅ ] APP {} is newer than the latest release ('{}' > '{}'), skipping.",current.get_app_name(),current.tag_name,latest.tag_name);
+            return false
+        }
+        VersionComparison::Newer => {
+            println!("[⚠️ ] APP {} has a new version detected.",current.get_app_name());
+
+            // Version
+            println!("Version:\t'{}' -> '{}'",current.tag_name,latest.tag_name);
+            // Published date
+            println!("Published date: '{}' -> '{}'",current.published_at,latest.published_at);
+            // Source URL
+            println!("Source URL: '{}'",latest.html_url);
+            // Print notes
+            println!("Version notes:\n============\n{}\n============",latest.get_formated_body());
+        }
     }
     true
 }
 
-pub fn download_file_to_path(file_url: String, destination_dir: String){
+pub fn get_exit_code_explanation(exit_code: Option<i32>) -> String {
+    let vc_redist_url = "https://learn.microsoft.com/en-us/cpp/windows/latest-supported-vc-redist?view=msvc-170#latest-microsoft-visual-c-redistributable-version";
+
+    match exit_code {
+        Some(0) => "Exit code '0'. Patched successfully.".to_string(),
+        Some(-1073741701) => { //x86
+            format!("Exit code '-1073741701'. Some DLLs might be missing.\nRefer to here to install the Latest Microsoft Visual C++ Redistributable Version {vc_redist_url}")
+        }
+        Some(-1073741515) => { //x64
+            format!("Exit code '-1073741515'. Some 64bit DLLs might be missing.\nRefer to here to install the Latest Microsoft Visual C++ Redistributable Version {vc_redist_url}")
+        }
+        Some(unknown_code) => {
+            format!("Exit code '{unknown_code}'. Ensure that the mod's executables can be manually executed. Maybe DLL are missing, for 32/86bits and/or 64bits {vc_redist_url}")
+        }
+        None => "The patcher was terminated by a signal, no exit code returned.".to_string()
+    }
+}
+
+// Time to wait for each of the patcher's prompts to show up.
+pub const PATCHER_PROMPT_TIMEOUT: Duration = Duration::from_secs(30);
+// Time to wait for the patcher to finish once all the prompts have been answered.
+pub const PATCHER_EXIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn run_patcher(executable_filepath: &str, prompts: &[PatcherPrompt], patch_output: &mut PatchOutput) -> XrdResult<()> {
+    // Stdout and stderr are piped, otherwise they would be drawn on top of the TUI.
+    let mut child = Command::new(executable_filepath)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin_pipe = child.stdin.take();
+
+    // Prompts don't always end with a new line, hence stdout is read in chunks instead of lines.
+    let (stdout_sender, stdout_receiver) = mpsc::channel::<Vec<u8>>();
+    let mut child_stdout = child.stdout.take().ok_or_else(|| XrdError::Other("Couldn't read the patcher's output.".to_string()))?;
+    let stdout_thread = thread::spawn(move || {
+        let mut chunk = [0u8; 1024];
+        while let Ok(read_bytes) = child_stdout.read(&mut chunk) {
+            if read_bytes == 0 || stdout_sender.send(chunk[..read_bytes].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    let mut child_stderr = child.stderr.take().ok_or_else(|| XrdError::Other("Couldn't read the patcher's errors.".to_string()))?;
+    let stderr_thread = thread::spawn(move || {
+        let mut stderr_bytes: Vec<u8> = vec![];
+        let _ = child_stderr.read_to_end(&mut stderr_bytes);
+        stderr_bytes
+    });
+
+    let mut stdout_bytes: Vec<u8> = vec![];
+    let mut stdout_closed = false;
+    let mut search_from: usize = 0;
+
+    // Answer each prompt only once it has been printed.
+    for prompt in prompts {
+        let deadline = Instant::now() + PATCHER_PROMPT_TIMEOUT;
+        let mut prompt_found = false;
+
+        while !prompt_found {
+            // Only the output after the previous prompt is checked.
+            let lowercase_output = String::from_utf8_lossy(&stdout_bytes).to_lowercase();
+            if let Some((position, length)) = lowercase_output.get(search_from..).and_then(|pending_output| prompt.find_in(pending_output)) {
+                search_from += position + length;
+                prompt_found = true;
+                continue;
+            }
+
+            if stdout_closed {
+                break;
+            }
+
+            let remaining_time = deadline.saturating_duration_since(Instant::now());
+            if remaining_time.is_zero() {
+                break;
+            }
+
+            match stdout_receiver.recv_timeout(remaining_time) {
+                Ok(chunk) => stdout_bytes.extend(chunk),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => stdout_closed = true,
+            }
+        }
+
+        if !prompt_found {
+            if prompt.optional {
+                break;
+            }
+            let reason = match stdout_closed {
+                true => "exited before asking".to_string(),
+                false => format!("didn't ask within {} seconds", PATCHER_PROMPT_TIMEOUT.as_secs()),
+            };
+            patch_output.interaction_error = Some(format!(
+                "The patcher {reason} for '{}'. The patcher's interaction might have changed in a newer version of the mod, try executing '{executable_filepath}' manually.",
+                prompt.description
+            ));
+            break;
+        }
+
+        if let Some(stdin) = stdin_pipe.as_mut()
+            && let Err(e) = stdin.write_all(prompt.answer.as_bytes()).and_then(|_| stdin.flush()) {
+            patch_output.interaction_error = Some(format!("Couldn't answer the patcher's prompt '{}' <{e}>.", prompt.description));
+            break;
+        }
+    }
+
+    // Dropping the pipe closes the patcher's stdin.
+    drop(stdin_pipe);
+
+    // Wait for the patcher to exit, kill it otherwise.
+    let deadline = match patch_output.interaction_error.is_some() {
+        true => Instant::now(),
+        false => Instant::now() + PATCHER_EXIT_TIMEOUT,
+    };
+    let exit_status = loop {
+        if let Some(exit_status) = child.try_wait()? {
+            break Some(exit_status);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            if patch_output.interaction_error.is_none() {
+                patch_output.interaction_error = Some(format!(
+                    "The patcher didn't finish within {} seconds and was stopped. The patcher's interaction might have changed in a newer version of the mod, try executing '{executable_filepath}' manually.",
+                    PATCHER_EXIT_TIMEOUT.as_secs()
+                ));
+            }
+            break None;
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    let _ = stdout_thread.join();
+    stdout_bytes.extend(stdout_receiver.try_iter().flatten());
+    let stderr_bytes = stderr_thread.join().unwrap_or_default();
+
+    patch_output.exit_code = exit_status.and_then(|status| status.code());
+    patch_output.stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
+    patch_output.stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+    Ok(())
+}
+
+// Returns the path of the downloaded file, named after the last segment of the URL.
+pub fn download_file_to_path(file_url: String, destination_dir: String) -> XrdResult<String> {
     // Download overlay.zip
     let file_to_download = Download::new(&file_url);
-    let destination_file_path = &format!("{}/{}", destination_dir, file_to_download.file_name.to_str().unwrap().to_string());
+    let destination_file_path = &format!("{}/{}", destination_dir, file_to_download.file_name.to_string_lossy());
 
     // Check if file already exists
     let mut is_present:bool=Path::new(destination_file_path).exists();
@@ -87,12 +455,11 @@ pub fn download_file_to_path(file_url: String, destination_dir: String){
     match (is_present,is_dir) {
         (true,false) => {
             // println!("A file with the name '{}' already exists, proceeding with the deletion.",destination_file_path);
-            fs::remove_file(destination_file_path);
+            fs::remove_file(destination_file_path)?;
         }
         (true,true) => {
             // Error won't delete a folder
-            // println!("The file '{}' cannot be downloaded due to a directory having the exact same name.",destination_file_path);
-            exit(1);
+            return Err(XrdError::Other(format!("The file '{}' cannot be downloaded due to a directory having the exact same name.",destination_file_path)));
         }
         _ => {}
 
@@ -106,45 +473,67 @@ pub fn download_file_to_path(file_url: String, destination_dir: String){
         .connect_timeout(Duration::from_secs(4))
         .download_folder(Path::new(&destination_dir))
         .parallel_requests(8)
-        .build()
-        .unwrap();
+        .build()?;
 
-    let response = dl.download(&[file_to_download]).unwrap(); // other error handling
+    let response = dl.download(&[file_to_download])?;
 
-    response.iter().for_each(|v| match v {
-        Ok(v) => {}
-            // println!("Downloaded: {:?}", v),
-        Err(e) => {} //println!("Error: {:?}", e),
-    });
+    for download_result in response {
+        download_result?;
+    }
+    Ok(destination_file_path.to_string())
 }
 
-pub fn unzip_file(zip_file_path: String, unzip_dir:String){
+// Copies the files and folders inside source_dir into destination_dir, overwriting existing files.
+pub fn copy_dir_contents(source_dir: &String, destination_dir: &String) -> XrdResult<()> {
+    fs::create_dir_all(destination_dir)?;
+    for entry in fs::read_dir(source_dir)? {
+        let entry = entry?;
+        let source_path = entry.path().to_string_lossy().to_string();
+        let destination_path = format!("{}/{}", destination_dir, entry.file_name().to_string_lossy());
+        match entry.file_type()?.is_dir() {
+            true => copy_dir_contents(&source_path, &destination_path)?,
+            false => { fs::copy(&source_path, &destination_path)?; }
+        }
+    }
+    Ok(())
+}
+
+// Formatted as "sha256:<hex>", like the digests shown by GitHub.
+pub fn get_file_sha256(file_path: &String) -> XrdResult<String> {
+    let mut file = File::open(file_path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+pub fn unzip_file(zip_file_path: String, unzip_dir:String) -> XrdResult<()> {
     // this was a copy pasta from somewhere
 
-    let zipfile = File::open(&zip_file_path).unwrap();
+    let zipfile = File::open(&zip_file_path)?;
 
-    let mut archive = ZipArchive::new(zipfile).unwrap();
+    let mut archive = ZipArchive::new(zipfile)?;
 
     for i in 0..archive.len() {
-        let mut file = archive.by_index(i).unwrap();
+        let mut file = archive.by_index(i)?;
         let outpath = format!("{}/{}",unzip_dir,file.name());
 
-        {
-            let comment = file.comment();
-            if !comment.is_empty() {
-                println!("File {i} comment: {comment}");
-            }
-        }
+        // {
+        //     let comment = file.comment();
+        //     if !comment.is_empty() {
+        //         println!("File {i} comment: {comment}");
+        //     }
+        // }
 
         if file.is_dir() {
             // println!("File {} extracted to \"{}\"", i, outpath);
-            fs::create_dir_all(&outpath).unwrap();
+            fs::create_dir_all(&outpath)?;
         } else {
             // println!("File {} extracted to \"{}\" ({} bytes)",i,outpath,file.size());
-            let mut outfile = fs::File::create(&outpath).unwrap();
-            io::copy(&mut file, &mut outfile).unwrap();
+            let mut outfile = fs::File::create(&outpath)?;
+            io::copy(&mut file, &mut outfile)?;
         }
     }
+    Ok(())
 
     // println!("File '{}' extracted to '{}'",zip_file_path,unzip_dir);
 }
\ No newline at end of file
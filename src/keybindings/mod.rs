@@ -0,0 +1,265 @@
+// Keys of the TUI, set under "keybindings" in settings.json:
+// {
+//     "keybindings": {
+//         "vim_navigation": true,
+//         "keys": { "quit": ["q", "Esc"], "search_updates": ["F5"] }
+//     }
+// }
+// The keys listed for an action replace its default ones.
+
+use std::collections::{BTreeMap, HashMap};
+use ratatui::crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, IntoEnumIterator};
+use crate::error::{XrdError, XrdResult};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Display, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Action {
+    // General
+    Quit,
+    NextTab,
+    PreviousTab,
+    SelectNext,
+    SelectPrevious,
+    SelectFirst,
+    SelectLast,
+    Help,
+    Save,
+    Reload,
+    // Mod lists
+    ToggleEnabled,
+    Mark,
+    MarkAll,
+    Search,
+    FilterOutdated,
+    FilterPatched,
+    FilterType,
+    FilterOwner,
+    ClearFilters,
+    // Updates
+    SearchUpdates,
+    UpdateSelected,
+    UpdateAll,
+    Reinstall,
+    Uninstall,
+    ScrollNotesDown,
+    ScrollNotesUp,
+    // Patching
+    Patch,
+    RestoreGameFiles,
+    // Settings
+    UseSelectedLibrary,
+    EditGameFolder,
+    DetectGameFolder,
+    SwitchProfile,
+    ToggleOfflineMode,
+    SwitchTheme,
+}
+
+impl Action {
+    pub(crate) fn get_description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::NextTab => "Next tab",
+            Action::PreviousTab => "Previous tab",
+            Action::SelectNext => "Move down",
+            Action::SelectPrevious => "Move up",
+            Action::SelectFirst => "Go to the first row",
+            Action::SelectLast => "Go to the last row",
+            Action::Help => "Show all the keys",
+            Action::Save => "Save the configuration",
+            Action::Reload => "Reload the configuration",
+            Action::ToggleEnabled => "Enable/disable the selected mods",
+            Action::Mark => "Mark the highlighted mod",
+            Action::MarkAll => "Mark/unmark all the listed mods",
+            Action::Search => "Search the list",
+            Action::FilterOutdated => "Only list the mods with a new version",
+            Action::FilterPatched => "Only list the patched mods",
+            Action::FilterType => "Filter by mod type",
+            Action::FilterOwner => "Filter by repository owner",
+            Action::ClearFilters => "Clear the search and filters",
+            Action::SearchUpdates => "Search updates",
+            Action::UpdateSelected => "Update the selected mods",
+            Action::UpdateAll => "Update all the enabled mods",
+            Action::Reinstall => "Reinstall the selected mod",
+            Action::Uninstall => "Uninstall the selected mods",
+            Action::ScrollNotesDown => "Scroll the release notes down",
+            Action::ScrollNotesUp => "Scroll the release notes up",
+            Action::Patch => "Patch the selected mods",
+            Action::RestoreGameFiles => "Restore the unpatched game files",
+            Action::UseSelectedLibrary => "Use the selected Steam library",
+            Action::EditGameFolder => "Type the game folder",
+            Action::DetectGameFolder => "Detect the game folder automatically",
+            Action::SwitchProfile => "Switch to the next profile",
+            Action::ToggleOfflineMode => "Toggle the offline mode",
+            Action::SwitchTheme => "Switch to the next theme",
+        }
+    }
+
+    fn get_default_keys(self) -> Vec<KeyCode> {
+        let letter = |c: char| vec![KeyCode::Char(c), KeyCode::Char(c.to_ascii_uppercase())];
+        match self {
+            Action::Quit => vec![KeyCode::Char('q'), KeyCode::Char('Q'), KeyCode::Esc],
+            Action::NextTab => vec![KeyCode::Right],
+            Action::PreviousTab => vec![KeyCode::Left],
+            Action::SelectNext => vec![KeyCode::Down],
+            Action::SelectPrevious => vec![KeyCode::Up],
+            Action::SelectFirst => vec![KeyCode::Home],
+            Action::SelectLast => vec![KeyCode::End],
+            Action::Help => vec![KeyCode::Char('?')],
+            Action::Save => letter('s'),
+            Action::Reload => letter('r'),
+            Action::ToggleEnabled => vec![KeyCode::Enter],
+            Action::Mark => vec![KeyCode::Char(' ')],
+            Action::MarkAll => letter('a'),
+            Action::Search => vec![KeyCode::Char('/')],
+            Action::FilterOutdated => letter('o'),
+            Action::FilterPatched => letter('p'),
+            Action::FilterType => letter('t'),
+            Action::FilterOwner => letter('w'),
+            Action::ClearFilters => letter('c'),
+            Action::SearchUpdates => letter('s'),
+            Action::UpdateSelected => vec![KeyCode::Enter],
+            Action::UpdateAll => letter('u'),
+            Action::Reinstall => letter('i'),
+            Action::Uninstall => letter('x'),
+            Action::ScrollNotesDown => vec![KeyCode::PageDown],
+            Action::ScrollNotesUp => vec![KeyCode::PageUp],
+            Action::Patch => [letter('p'), vec![KeyCode::Enter]].concat(),
+            Action::RestoreGameFiles => letter('u'),
+            Action::UseSelectedLibrary => vec![KeyCode::Enter],
+            Action::EditGameFolder => letter('e'),
+            Action::DetectGameFolder => letter('c'),
+            Action::SwitchProfile => letter('p'),
+            Action::ToggleOfflineMode => letter('o'),
+            Action::SwitchTheme => letter('t'),
+        }
+    }
+
+    fn get_vim_keys(self) -> Vec<KeyCode> {
+        match self {
+            Action::SelectNext => vec![KeyCode::Char('j')],
+            Action::SelectPrevious => vec![KeyCode::Char('k')],
+            Action::SelectFirst => vec![KeyCode::Char('g')],
+            Action::SelectLast => vec![KeyCode::Char('G')],
+            _ => vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct KeybindingsConfig {
+    // j/k to move, g/G to go to the first/last row.
+    #[serde(default)]
+    pub(crate) vim_navigation: bool,
+    // Key names ("q", "Enter", "PageDown", "F5"...) of each action, replacing the defaults.
+    #[serde(default)]
+    pub(crate) keys: BTreeMap<Action, Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap { keys: Action::iter().map(|action| (action, action.get_default_keys())).collect() }
+    }
+}
+
+impl Keymap {
+    // action_groups are the actions available at the same time (those of each tab), which can't share a key.
+    pub(crate) fn from_config(keybindings_config: &KeybindingsConfig, action_groups: &[&[Action]]) -> XrdResult<Keymap> {
+        let mut keymap = Keymap::default();
+        for (action, key_names) in &keybindings_config.keys {
+            let keys = key_names.iter()
+                .map(|key_name| parse_key(key_name).ok_or_else(|| XrdError::InvalidKeybinding { action: action.to_string(), key_name: key_name.to_string() }))
+                .collect::<XrdResult<Vec<KeyCode>>>()?;
+            keymap.keys.insert(*action, keys);
+        }
+        if keybindings_config.vim_navigation {
+            for action in Action::iter() {
+                keymap.keys.entry(action).or_default().extend(action.get_vim_keys());
+            }
+        }
+        for actions in action_groups {
+            keymap.check_duplicate_keys(actions)?;
+        }
+        Ok(keymap)
+    }
+
+    // Otherwise the key would only trigger the first of the actions.
+    fn check_duplicate_keys(&self, actions: &[Action]) -> XrdResult<()> {
+        for (index, action) in actions.iter().enumerate() {
+            for other_action in &actions[index + 1..] {
+                if let Some(key_code) = self.get_keys(*action).iter().find(|key_code| self.get_keys(*other_action).contains(key_code)) {
+                    return Err(XrdError::DuplicateKeybinding {
+                        key_name: format_key(*key_code),
+                        action: action.to_string(),
+                        other_action: other_action.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get_keys(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    // First of the actions bound to the key, as a key can do something else in each tab.
+    pub(crate) fn get_action(&self, actions: &[Action], key_code: KeyCode) -> Option<Action> {
+        actions.iter().copied().find(|action| self.get_keys(*action).contains(&key_code))
+    }
+
+    // "q/Q/Esc"
+    pub(crate) fn describe_keys(&self, action: Action) -> String {
+        self.get_keys(action).iter().map(|key_code| format_key(*key_code)).collect::<Vec<String>>().join("/")
+    }
+}
+
+// Named keys are case insensitive, single characters are taken as they are.
+pub fn parse_key(key_name: &str) -> Option<KeyCode> {
+    let mut chars = key_name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    let key_code = match key_name.to_lowercase().as_str() {
+        "enter" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        function_key => KeyCode::F(function_key.strip_prefix('f')?.parse().ok()?),
+    };
+    Some(key_code)
+}
+
+pub fn format_key(key_code: KeyCode) -> String {
+    match key_code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(number) => format!("F{number}"),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        other => format!("{other:?}"),
+    }
+}
@@ -0,0 +1,79 @@
+// Lockfile with the exact versions of the enabled mods, to reproduce the same setup on another machine.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use crate::error::{XrdError, XrdResult};
+use crate::functions::write_file_atomically;
+use crate::stuff::{AppStruct, AppType};
+
+pub const LOCKFILE_VERSION: u64 = 1;
+pub const DEFAULT_LOCKFILE_NAME: &str = "xrd_mods.lock.json";
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct LockedApp {
+    pub(crate) repo_owner: String,
+    pub(crate) repo_name: String,
+    #[serde(default)]
+    pub(crate) app_type: AppType,
+    // Release id, changes if the release is published again under the same tag.
+    pub(crate) id: i32,
+    pub(crate) tag_name: String,
+    #[serde(default)]
+    pub(crate) published_at: String,
+    #[serde(default)]
+    pub(crate) url_source_version: String,
+    // Asset file name -> "sha256:<hex>"
+    #[serde(default)]
+    pub(crate) asset_hashes: BTreeMap<String, String>,
+}
+
+impl LockedApp {
+    pub(crate) fn from_app(app: &AppStruct, asset_hashes: HashMap<String, String>) -> LockedApp {
+        LockedApp {
+            repo_owner: app.repo_owner.to_string(),
+            repo_name: app.repo_name.to_string(),
+            app_type: app.app_type.clone(),
+            id: app.id,
+            tag_name: app.tag_name.to_string(),
+            published_at: app.published_at.to_string(),
+            url_source_version: app.url_source_version.to_string(),
+            asset_hashes: asset_hashes.into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Lockfile {
+    pub(crate) lockfile_version: u64,
+    // Version of the tool that exported it.
+    #[serde(default)]
+    pub(crate) tool_version: String,
+    // BTreeMap to keep the file diffable.
+    #[serde(default)]
+    pub(crate) apps: BTreeMap<String, LockedApp>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Lockfile {
+            lockfile_version: LOCKFILE_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            apps: BTreeMap::new(),
+        }
+    }
+}
+
+pub fn read_lockfile(lockfile_path: &String) -> XrdResult<Lockfile> {
+    let contents = fs::read_to_string(lockfile_path)?;
+    let lockfile: Lockfile = serde_json::from_str(&contents)?;
+    if lockfile.lockfile_version > LOCKFILE_VERSION {
+        return Err(XrdError::Other(format!("The lockfile '{lockfile_path}' uses the version {}, but this version of the tool only supports up to {LOCKFILE_VERSION}. Update the tool", lockfile.lockfile_version)));
+    }
+    Ok(lockfile)
+}
+
+pub fn write_lockfile(lockfile_path: &String, lockfile: &Lockfile) -> XrdResult<()> {
+    let contents = serde_json::to_string_pretty(lockfile)?;
+    write_file_atomically(lockfile_path, contents.as_bytes())
+}
@@ -10,10 +10,23 @@ mod actual_new_ratatui_app;
 mod manager;
 use manager::*;
 mod functions;
+mod error;
+mod vdf;
+mod cli;
+mod migrations;
+mod storage;
+mod lockfile;
+mod cache;
+mod watch;
+mod markdown;
+mod version;
+mod keybindings;
+mod theme;
 
 
 use color_eyre::Result;
 use futures::Stream;
+use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use ratatui::style::Stylize;
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -48,11 +61,33 @@ fn main() -> Result<()> {
     // let _ = Confirm::new("Done").
     //     with_default(true).
     //     with_help_message("Press enter to exit...").prompt();
-    println!("hi");
     color_eyre::install()?;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::run_command(&args) {
+        Ok(true) => return Ok(()),
+        Ok(false) => {} // No command, launch the TUI
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    }
+
+    println!("hi");
     let terminal = ratatui::init();
-    let app_result = crate::actual_new_ratatui_app::App::default().run(terminal);
+    // The hook of ratatui::init restores the terminal on panic, but leaves the mouse captured.
+    let restore_terminal_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = ratatui::crossterm::execute!(std::io::stdout(), DisableMouseCapture);
+        restore_terminal_hook(panic_info);
+    }));
+    let app_result = match ratatui::crossterm::execute!(std::io::stdout(), EnableMouseCapture) {
+        Ok(()) => crate::actual_new_ratatui_app::App::default().run(terminal),
+        Err(e) => Err(e.into()),
+    };
     // let app_result = crate::actual_new_ratatui_app::App::default().run();
+    // The terminal is restored whatever happened, otherwise it's left in raw mode.
+    let _ = ratatui::crossterm::execute!(std::io::stdout(), DisableMouseCapture);
     ratatui::restore();
     println!("bye");
     app_result
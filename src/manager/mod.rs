@@ -1,5 +1,10 @@
+use crate::error::{XrdError, XrdResult};
 use crate::stuff::*;
 use crate::functions::*;
+use crate::migrations::{get_schema_version, migrate_config, CURRENT_SCHEMA_VERSION};
+use crate::storage::{get_storage_paths, merge_config_values, split_config_value};
+use crate::lockfile::{LockedApp, Lockfile};
+use crate::cache::{CachedRelease, CachedReleaseList, ReleaseCache};
 
 use std::fmt::{format, Write as StdinWrite};
 use std::{fs, io};
@@ -7,7 +12,7 @@ use std::collections::HashMap;
 use std::fs::{File, create_dir, create_dir_all, Permissions};
 use std::io::{Error, Read, Seek, Write};
 use std::path::Path;
-use std::process::{exit, Stdio};
+use std::process::Stdio;
 use futures::future::{err, ok, SelectAll};
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -33,109 +38,426 @@ use std::os::unix::fs::PermissionsExt;
 // Get path from Windows registry
 use winreg::{RegKey,enums::*};
 
+// Result of each patch applied, by app name.
+pub type PatchResults = Vec<(String, XrdResult<PatchOutput>)>;
+
+// Stored next to the backup of GuiltyGearXrd.exe, to tell whether the game executable changed since.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct GameBackupInfo {
+    // Of the unpatched executable, empty if no clean backup could be made.
+    pub(crate) sha256: String,
+    pub(crate) size: u64,
+    // Of the executable after the last patch. Any other one was replaced by Steam or patched outside this tool.
+    pub(crate) patched_sha256: String,
+}
+
 #[derive(Default, Clone)]
 pub struct Manager {
-    pub(crate) config: Config
+    pub(crate) config: Config,
+    // Contents of each config file when it was last loaded or saved, used to detect changes from other instances.
+    last_synced_contents: HashMap<String, Option<String>>,
 }
 
 impl Manager {
-    pub(crate) fn load_config(&mut self) -> std::io::Result<()> {
-        // load config from db.json.
+    pub(crate) fn load_config(&mut self) -> XrdResult<()> {
+        // load config from the settings and state files (db.json in portable mode).
         // otherwise load default config.
 
-        let mut is_present:bool=Path::new(&self.config.get_db_file_path()).exists();
+        let storage_paths = get_storage_paths()?;
+        let config_files = storage_paths.get_config_files();
 
-        match is_present {
-            false => {
+        let files_contents: Vec<Option<String>> = {
+            let _lock = match Path::new(&storage_paths.settings_file).exists() {
+                true => Some(acquire_file_lock(&storage_paths.get_lock_file_path(), false)?),
+                false => None
+            };
+            let mut files_contents: Vec<Option<String>> = vec![];
+            for file_path in &config_files {
+                files_contents.push(read_optional_file(file_path)?);
+            }
+            files_contents
+        };
+        self.last_synced_contents = config_files.iter().cloned().zip(files_contents.iter().cloned()).collect();
+
+        let mut raw_files: Vec<Option<Value>> = vec![];
+        for contents in &files_contents {
+            raw_files.push(match contents {
+                Some(contents) => Some(serde_json::from_str(contents)?),
+                None => None
+            });
+        }
+
+        // Refuse any file written by a newer version, before merging them.
+        for raw_file in raw_files.iter().flatten() {
+            let found_version = get_schema_version(raw_file);
+            if found_version > CURRENT_SCHEMA_VERSION {
+                return Err(XrdError::ConfigTooNew { found_version, supported_version: CURRENT_SCHEMA_VERSION });
+            }
+        }
+
+        let mut raw_files_iter = raw_files.iter().cloned();
+        let raw_settings = raw_files_iter.next().flatten();
+        let raw_state = raw_files_iter.next().flatten();
+
+        let raw_settings = match raw_settings {
+            Some(raw_settings) => raw_settings,
+            None if raw_state.is_none() => {
                 // println!("DB not found. Loading defaults.");
+                self.config = Config::default();
                 self.config.set_default_apps();
+                return Ok(());
             }
-            _ => {
-                let mut file = File::open(self.config.get_db_file_path())?; // Open file
-                let mut contents = String::new();
-                file.read_to_string(&mut contents)?;
-                self.config = serde_json::from_str(&contents)?;
+            None => {
+                // Only the state is left, the settings start from the defaults.
+                let mut default_config = Config::default();
+                default_config.set_default_apps();
+                default_config.schema_version = CURRENT_SCHEMA_VERSION;
+                split_config_value(serde_json::to_value(&default_config)?).0
+            }
+        };
+
+        let raw_config = merge_config_values(raw_settings, raw_state);
+        let found_version = get_schema_version(&raw_config);
+        self.config = serde_json::from_value(migrate_config(raw_config)?)?;
+
+        // Keep the original files before overwriting them with the upgraded ones.
+        if found_version < CURRENT_SCHEMA_VERSION {
+            for (file_path, contents) in config_files.iter().zip(files_contents.iter()) {
+                let Some(contents) = contents else { continue };
+                let backup_file_path = format!("{}.v{}.bak", file_path, found_version);
+                if !Path::new(&backup_file_path).exists() {
+                    fs::write(&backup_file_path, contents)?;
+                }
             }
+            self.save_config()?;
         }
 
         Ok(())
     }
 
-    pub fn save_config(&mut self) -> std::io::Result<()>  {
-        let mut file = File::create(self.config.get_db_file_path())?;
+    pub fn save_config(&mut self) -> XrdResult<()>  {
+        self.config.schema_version = CURRENT_SCHEMA_VERSION;
+        self.config.sync_active_profile();
+        let storage_paths = get_storage_paths()?;
+
+        if let Some(settings_dir) = Path::new(&storage_paths.settings_file).parent() {
+            create_dir_all(settings_dir)?;
+        }
+        let _lock = acquire_file_lock(&storage_paths.get_lock_file_path(), true)?;
+
+        // Refuse to overwrite changes done by another instance since this one loaded the files.
+        for file_path in storage_paths.get_config_files() {
+            let current_contents = read_optional_file(&file_path)?;
+            if current_contents != self.last_synced_contents.get(&file_path).cloned().flatten() {
+                return Err(XrdError::ConfigModified(file_path));
+            }
+        }
+
+        let config_value = serde_json::to_value(&self.config)?;
+        let files_values: Vec<Value> = match storage_paths.portable {
+            true => vec![config_value],
+            false => {
+                let (settings_value, state_value) = split_config_value(config_value);
+                vec![settings_value, state_value]
+            }
+        };
 
-        let config_string = serde_json::to_string(&self.config)?;
-        file.write_all(config_string.as_bytes())?;
+        for (file_path, file_value) in storage_paths.get_config_files().into_iter().zip(files_values) {
+            if let Some(file_dir) = Path::new(&file_path).parent() {
+                create_dir_all(file_dir)?;
+            }
+            // Pretty printed, so it can be edited by hand.
+            let file_string = serde_json::to_string_pretty(&file_value)?;
+            write_file_atomically(&file_path, file_string.as_bytes())?;
+            self.last_synced_contents.insert(file_path, Some(file_string));
+        }
         Ok(())
     }
 
-    fn get_latest_tags_hash_map(&self) -> HashMap<String, TagInfo> {
+    pub(crate) fn get_mod_dir_path(&self, app_name: &String) -> XrdResult<String> {
+        Ok(get_storage_paths()?.get_mod_dir_path(app_name))
+    }
+
+    fn get_latest_tags_hash_map(&self) -> XrdResult<HashMap<String, TagInfo>> {
         // TODO remove
         let mut tags_hashmap:HashMap<String, TagInfo> =HashMap::new();
         for app_struct in self.config.apps.values() {
-            let result = app_struct.get_latest_tag();
-            match result {
-                Ok(new_tag) => {
-                    tags_hashmap.insert(app_struct.get_app_name(), new_tag);
-                }
-                Err(e) => {
-                    println!("Error getting tag for app '{}': << {} >>", app_struct.get_app_name(), e);
-                    exit(1);
-                }
+            let new_tag = self.get_latest_release(&app_struct.get_app_name())
+                .map_err(|e| XrdError::Other(format!("Error getting tag for app '{}': << {} >>", app_struct.get_app_name(), e)))?;
+            tags_hashmap.insert(app_struct.get_app_name(), new_tag.tag_info);
+        }
+        Ok(tags_hashmap)
+    }
+
+    fn get_release_cache(&self, app_name: &String) -> XrdResult<ReleaseCache> {
+        Ok(ReleaseCache::new(&get_storage_paths()?.cache_dir, app_name, self.config.offline_mode))
+    }
+
+    // Fetched from GitHub and cached, or taken from the cache in offline mode.
+    pub(crate) fn get_latest_release(&self, app_name: &String) -> XrdResult<CachedRelease> {
+        let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        let release_cache = self.get_release_cache(app_name)?;
+        match self.config.offline_mode {
+            true => release_cache.get_latest_release(),
+            false => release_cache.store_release(app.get_latest_tag()?, true),
+        }
+    }
+
+    // Same as get_latest_release, for a given tag.
+    pub(crate) fn get_release(&self, app_name: &String, tag_name: &String) -> XrdResult<CachedRelease> {
+        let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        let release_cache = self.get_release_cache(app_name)?;
+        match self.config.offline_mode {
+            true => release_cache.get_release(tag_name),
+            false => release_cache.store_release(app.get_tag(tag_name)?, false),
+        }
+    }
+
+    // Same as get_latest_release, for the list of the most recent releases.
+    pub(crate) fn get_releases(&self, app_name: &String) -> XrdResult<CachedReleaseList> {
+        let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        let release_cache = self.get_release_cache(app_name)?;
+        match self.config.offline_mode {
+            true => release_cache.get_releases(),
+            false => release_cache.store_releases(app.get_releases()?),
+        }
+    }
+
+    // Releases between the installed and the latest one, newest first.
+    pub(crate) fn get_releases_since_installed(&self, app_name: &String, latest: &TagInfo) -> XrdResult<Vec<TagInfo>> {
+        let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        if !app.has_new_version(latest) {
+            return Ok(vec![latest.clone()]);
+        }
+        Ok(app.get_releases_since_installed(&self.get_releases(app_name)?.releases, latest))
+    }
+
+    pub(crate) fn patch_app(&mut self, app_name: String) -> XrdResult<PatchOutput> {
+        let modpath_dir = &self.get_mod_dir_path(&app_name)?;
+        let xrd_game_folder = self.config.get_xrd_game_folder()?;
+
+        let app = self.config.apps.get_mut(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        if !app.is_patchable() {
+            return Err(XrdError::PatchNotSupported(app.get_app_name()));
+        }
+
+        let backup_warning = self.backup_game_executable()?;
+
+        let app = self.config.apps.get_mut(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        let mut patch_output = app.patch_app(xrd_game_folder, modpath_dir)?;
+        if patch_output.is_successful() {
+            app.patched = true;
+        }
+        patch_output.backup_warning = backup_warning;
+        self.record_patched_game_executable()?;
+        Ok(patch_output)
+    }
+
+    fn get_game_backup_info(&self) -> XrdResult<Option<GameBackupInfo>> {
+        match read_optional_file(&get_storage_paths()?.get_game_backup_info_file_path())? {
+            Some(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn save_game_backup_info(&self, game_backup_info: &GameBackupInfo) -> XrdResult<()> {
+        let game_backup_info_file_path = get_storage_paths()?.get_game_backup_info_file_path();
+        if let Some(backup_dir) = Path::new(&game_backup_info_file_path).parent() {
+            create_dir_all(backup_dir)?;
+        }
+        write_file_atomically(&game_backup_info_file_path, serde_json::to_string_pretty(game_backup_info)?.as_bytes())
+    }
+
+    // Copy of GuiltyGearXrd.exe before any patch is applied, used to revert the patches.
+    // Returns a warning when the executable is already patched, as no clean copy can be made then.
+    fn backup_game_executable(&mut self) -> XrdResult<Option<String>> {
+        let backup_file_path = get_storage_paths()?.get_game_backup_file_path();
+        let xrd_executable_path = get_xrd_executable_path(&self.config.get_xrd_game_folder()?);
+        let xrd_executable_sha256 = get_file_sha256(&xrd_executable_path)?;
+        let game_backup_info = self.get_game_backup_info()?.unwrap_or_default();
+
+        // Still the executable the backup was made from, or the one patched from it.
+        let has_clean_backup = !game_backup_info.sha256.is_empty() && Path::new(&backup_file_path).is_file();
+        if has_clean_backup && [&game_backup_info.sha256, &game_backup_info.patched_sha256].contains(&&xrd_executable_sha256) {
+            return Ok(None);
+        }
+
+        // A patched executable isn't a valid backup.
+        if xrd_executable_sha256 == game_backup_info.patched_sha256 || self.config.apps.values().any(|app| app.patched) {
+            if Path::new(&backup_file_path).exists() {
+                fs::remove_file(&backup_file_path)?;
             }
+            self.save_game_backup_info(&GameBackupInfo { patched_sha256: xrd_executable_sha256, ..GameBackupInfo::default() })?;
+            return Ok(Some(format!(
+                "'{xrd_executable_path}' is already patched, so no clean backup of it could be made and the patches can't be reverted. Verify the integrity of the game files through Steam to remove them"
+            )));
+        }
+
+        if let Some(backup_dir) = Path::new(&backup_file_path).parent() {
+            create_dir_all(backup_dir)?;
         }
-        tags_hashmap
+        fs::copy(&xrd_executable_path, &backup_file_path)?;
+        self.save_game_backup_info(&GameBackupInfo {
+            sha256: xrd_executable_sha256,
+            size: fs::metadata(&backup_file_path)?.len(),
+            patched_sha256: String::new(),
+        })
+        .map(|_| None)
     }
 
-    fn patch_app(&mut self, app_name: String) {
-        let modpath_dir = &format!("{}/{}", self.config.get_db_dir_path(), app_name);
-        let xrd_game_folder = self.config.get_xrd_game_folder().to_string();
+    fn record_patched_game_executable(&mut self) -> XrdResult<()> {
+        let mut game_backup_info = self.get_game_backup_info()?.unwrap_or_default();
+        let xrd_executable_sha256 = get_file_sha256(&get_xrd_executable_path(&self.config.get_xrd_game_folder()?))?;
+        if xrd_executable_sha256 != game_backup_info.sha256 {
+            game_backup_info.patched_sha256 = xrd_executable_sha256;
+            self.save_game_backup_info(&game_backup_info)?;
+        }
+        Ok(())
+    }
 
-        let mut app = self.config.apps.get_mut(&app_name).unwrap();
-        match app.app_type {
-            AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::BackgroundGamepad => {
-                match app.patch_app(xrd_game_folder, modpath_dir) {
-                    Ok(_) => {app.patched=true;}
-                    Err(e) => {println!("Error when patching app '{}' '{e}'",app.get_app_name())}
-                }
+    // Returns the path of the backup if it can replace the current GuiltyGearXrd.exe.
+    // After a Steam update the backup is of an older version, and is refused.
+    pub(crate) fn get_valid_game_backup(&mut self) -> XrdResult<String> {
+        let backup_file_path = get_storage_paths()?.get_game_backup_file_path();
+        let game_backup_info = match self.get_game_backup_info()? {
+            Some(game_backup_info) if !game_backup_info.sha256.is_empty() && Path::new(&backup_file_path).is_file() => game_backup_info,
+            _ => return Err(XrdError::GameBackupNotFound(backup_file_path)),
+        };
+
+        if fs::metadata(&backup_file_path)?.len() != game_backup_info.size || get_file_sha256(&backup_file_path)? != game_backup_info.sha256 {
+            return Err(XrdError::GameBackupOutdated(format!("the backup '{backup_file_path}' was modified")));
+        }
+        let xrd_executable_path = get_xrd_executable_path(&self.config.get_xrd_game_folder()?);
+        let xrd_executable_sha256 = get_file_sha256(&xrd_executable_path)?;
+        if xrd_executable_sha256 != game_backup_info.sha256 && xrd_executable_sha256 != game_backup_info.patched_sha256 {
+            return Err(XrdError::GameBackupOutdated(format!("'{xrd_executable_path}' changed since the backup '{backup_file_path}' was made, by a Steam update or a patch applied outside this tool")));
+        }
+        Ok(backup_file_path)
+    }
+
+    // Reverts every patch, restoring the executable and removing the files copied to the game folder.
+    pub(crate) fn restore_game_files(&mut self) -> XrdResult<()> {
+        let backup_file_path = self.get_valid_game_backup()?;
+
+        let xrd_game_folder = self.config.get_xrd_game_folder()?;
+        fs::copy(&backup_file_path, get_xrd_executable_path(&xrd_game_folder))?;
+
+        for app in self.config.apps.values_mut() {
+            if app.patched {
+                remove_copied_patch_files(&xrd_game_folder, app)?;
+            }
+            app.patched = false;
+        }
+
+        if let Some(mut game_backup_info) = self.get_game_backup_info()? {
+            game_backup_info.patched_sha256 = String::new();
+            self.save_game_backup_info(&game_backup_info)?;
+        }
+        Ok(())
+    }
+
+    // Stores the current setup as a profile, and makes it the active one.
+    pub(crate) fn save_profile(&mut self, profile_name: String) {
+        self.config.active_profile = profile_name;
+        self.config.sync_active_profile();
+    }
+
+    pub(crate) fn delete_profile(&mut self, profile_name: &String) -> XrdResult<()> {
+        if self.config.active_profile == *profile_name {
+            return Err(XrdError::Other(format!("Profile '{profile_name}' is active, switch to another profile before deleting it")));
+        }
+        match self.config.profiles.remove(profile_name) {
+            Some(_) => Ok(()),
+            None => Err(XrdError::ProfileNotFound(profile_name.to_string())),
+        }
+    }
+
+    // Enables the profile's apps, and applies or reverts patches to match it.
+    // Returns the result of each patch applied.
+    pub(crate) fn switch_profile(&mut self, profile_name: String) -> XrdResult<PatchResults> {
+        let profile = self.config.profiles.get(&profile_name).cloned().ok_or(XrdError::ProfileNotFound(profile_name.to_string()))?;
+        self.config.sync_active_profile();
+        let current_profile = self.config.get_current_profile();
+
+        // Patches can't be undone one by one. Unless only new patches are added,
+        // the executable is restored and the profile's patches applied again.
+        let only_adds_patches = current_profile.patched_apps.iter().all(|app_name| profile.patched_apps.contains(app_name));
+        if !only_adds_patches {
+            self.restore_game_files()?;
+        }
+
+        let mut patch_results: PatchResults = vec![];
+        for app_name in &profile.patched_apps {
+            let already_patched = self.config.apps.get(app_name).is_some_and(|app| app.patched);
+            if !already_patched {
+                patch_results.push((app_name.to_string(), self.patch_app(app_name.to_string())));
             }
-            _ => {println!("[🚫] App '{}' of type {:?} doesn't have a patch procedure. Skipping", app.get_app_name(),app.app_type)}
         }
+
+        for app in self.config.apps.values_mut() {
+            app.enabled = profile.enabled_apps.contains(&app.get_app_name());
+        }
+        self.config.active_profile = profile_name;
+
+        Ok(patch_results)
+    }
+
+    pub(crate) fn update_app(&mut self, app_name: String, latest_tag_info: &TagInfo) -> XrdResult<()> {
+        let app = self.config.apps.get(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        // Local builds aren't replaced by updates, reinstall the app to go back to the GitHub releases.
+        if !app.has_new_version(latest_tag_info) {
+            // println!("[✅ ] APP {} is up to date, skipping...", app_name);
+            return Ok(());
+        }
+        self.install_app_release(app_name, latest_tag_info, None)
+    }
+
+    // Downloads the installed release again, from the cache if available.
+    // Local builds are replaced by the latest release.
+    pub(crate) fn reinstall_app(&mut self, app_name: String) -> XrdResult<()> {
+        let app = self.config.apps.get(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        if app.is_local_install() {
+            let latest_release = self.get_latest_release(&app_name)?;
+            return self.install_app_release(app_name, &latest_release.tag_info, None);
+        }
+        if app.tag_name.is_empty() {
+            return Err(XrdError::Other(format!("App '{app_name}' isn't installed")));
+        }
+        let tag_name = app.tag_name.to_string();
+        let release_cache = self.get_release_cache(&app_name)?;
+        // The cached release is enough, no need to check GitHub.
+        let cached_release = match release_cache.get_release(&tag_name) {
+            Ok(cached_release) => cached_release,
+            Err(_) => self.get_release(&app_name, &tag_name)?,
+        };
+        self.install_app_release(app_name, &cached_release.tag_info, None)
     }
 
-    pub(crate) fn update_app(&mut self, app_name: String, latest_tag_info: &TagInfo) -> std::io::Result<()> {
-        let db_dir_path = self.config.get_db_dir_path().to_string();
-        let modpath_dir = &format!("{}/{}", db_dir_path, app_name);
+    // Downloads the release and records it as installed.
+    fn install_app_release(&mut self, app_name: String, latest_tag_info: &TagInfo, expected_asset_hashes: Option<&HashMap<String, String>>) -> XrdResult<()> {
+        let modpath_dir = &self.get_mod_dir_path(&app_name)?;
         let mut is_dir:bool=Path::new(modpath_dir).is_dir();
 
         match is_dir {
             true => {}
             false => {
-                if let Err(e) = create_dir_all(modpath_dir) {
-                    // println!("Error: {}", e);
-                    // println!("Error creating dir.\nExiting...");
-                    // exit(1);
-                    panic!("{}",e);
-                }
+                create_dir_all(modpath_dir)?;
                 // println!("Created directory for the mod {} located at '{}'", app_name, modpath_dir)
             }
         }
 
-        let mut app_to_update = self.config.apps.get_mut(&app_name).unwrap();
+        let release_cache = self.get_release_cache(&app_name)?;
+        let mut app_to_update = self.config.apps.get_mut(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
 
         // App update (download new files)
-        if app_to_update.tag_name == latest_tag_info.tag_name.to_string() {
-            // println!("[✅ ] APP {} is up to date, skipping...", app_name);
-        } else {
-            // println!("[⚠️ ] Updating '{}'", app_name);
-            match app_to_update.app_type {
-                AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::WakeupTool | AppType::MirrorColorSelect | AppType::BackgroundGamepad  => {
-                    app_to_update.download_mod(modpath_dir, latest_tag_info);
-                }
-                _ => {
-                    // TODO idk how to render this for the user
-                    // println!("[🚫] App '{}' of type {:?} doesn't have a update procedure. Skipping", app_name, app_to_update.app_type)
-                }
+        // println!("[⚠️ ] Updating '{}'", app_name);
+        match app_to_update.app_type {
+            AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::WakeupTool | AppType::MirrorColorSelect | AppType::BackgroundGamepad  => {
+                app_to_update.download_mod(modpath_dir, latest_tag_info, expected_asset_hashes, &release_cache)?;
+            }
+            _ => {
+                // TODO idk how to render this for the user
+                // println!("[🚫] App '{}' of type {:?} doesn't have a update procedure. Skipping", app_name, app_to_update.app_type)
             }
         }
 
@@ -143,11 +465,144 @@ impl Manager {
         app_to_update.published_at = latest_tag_info.published_at.to_string();
         app_to_update.url_source_version = latest_tag_info.html_url.to_string();
         app_to_update.id = latest_tag_info.id;
+        app_to_update.local_source = String::new();
         Ok(())
     }
 
-    pub(crate) fn update_all(&mut self){
-        let tags_hashmap: HashMap<String, TagInfo> = self.get_latest_tags_hash_map();
+    // Removes the downloaded files of the apps, and reverts their patches.
+    // Patches can't be undone one by one, so the executable is restored and the patches of the other apps applied again.
+    // Without a valid backup the executable stays patched, and a warning is returned.
+    // Returns the result of each patch applied again.
+    pub(crate) fn uninstall_apps(&mut self, app_names: &[String]) -> XrdResult<(PatchResults, Option<String>)> {
+        let mut reverts_patches = false;
+        for app_name in app_names {
+            let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+            reverts_patches |= app.patched;
+        }
+
+        let mut patch_results: PatchResults = vec![];
+        let mut warning: Option<String> = None;
+        if reverts_patches {
+            let app_names_to_patch_again: Vec<String> = self.config.apps.values()
+                .filter(|app| app.patched && !app_names.contains(&app.get_app_name()))
+                .map(|app| app.get_app_name())
+                .collect();
+            match self.restore_game_files() {
+                Ok(()) => {
+                    for app_name in app_names_to_patch_again {
+                        patch_results.push((app_name.to_string(), self.patch_app(app_name)));
+                    }
+                }
+                // Only the files copied by the uninstalled apps can be removed then.
+                Err(e @ (XrdError::GameBackupNotFound(_) | XrdError::GameBackupOutdated(_))) => {
+                    let xrd_game_folder = self.config.get_xrd_game_folder()?;
+                    for app_name in app_names {
+                        let app = self.config.apps.get(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+                        if app.patched {
+                            remove_copied_patch_files(&xrd_game_folder, app)?;
+                        }
+                    }
+                    warning = Some(format!("GuiltyGearXrd.exe is still patched by the uninstalled mods. {e}"));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        for app_name in app_names {
+            let modpath_dir = self.get_mod_dir_path(app_name)?;
+            if Path::new(&modpath_dir).is_dir() {
+                fs::remove_dir_all(&modpath_dir)?;
+            }
+            let app = self.config.apps.get_mut(app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+            app.tag_name = String::new();
+            app.published_at = String::new();
+            app.url_source_version = String::new();
+            app.id = 0;
+            app.local_source = String::new();
+            app.patched = false;
+        }
+        Ok((patch_results, warning))
+    }
+
+    // Installs a zip, executable or folder that isn't a GitHub release, such as a build shared on Discord.
+    // Patched afterward if automatically_patch is set, same as after an update.
+    pub(crate) fn install_local_app(&mut self, app_name: String, source_path: String) -> XrdResult<Option<PatchOutput>> {
+        let source_path = fs::canonicalize(&source_path)
+            .map_err(|e| XrdError::Other(format!("Couldn't open '{source_path}': {e}")))?
+            .to_string_lossy().to_string();
+        let modpath_dir = &self.get_mod_dir_path(&app_name)?;
+        create_dir_all(modpath_dir)?;
+
+        let app = self.config.apps.get_mut(&app_name).ok_or(XrdError::AppNotFound(app_name.to_string()))?;
+        app.install_local_mod(modpath_dir, &source_path)?;
+
+        app.tag_name = LOCAL_TAG_NAME.to_string();
+        app.published_at = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        app.url_source_version = String::new();
+        app.id = 0;
+        app.local_source = source_path;
+
+        match app.automatically_patch && !app.patched {
+            true => Ok(Some(self.patch_app(app_name)?)),
+            false => Ok(None),
+        }
+    }
+
+    pub(crate) fn export_lockfile(&self) -> XrdResult<Lockfile> {
+        let mut lockfile = Lockfile::default();
+        for app in self.config.apps.values() {
+            // Local builds can't be downloaded on other machines.
+            if app.enabled && !app.tag_name.is_empty() && !app.is_local_install() {
+                let asset_hashes = app.get_asset_hashes(&self.get_mod_dir_path(&app.get_app_name())?)?;
+                lockfile.apps.insert(app.get_app_name(), LockedApp::from_app(app, asset_hashes));
+            }
+        }
+        Ok(lockfile)
+    }
+
+    // Installs the exact versions of the lockfile, enabling only its apps.
+    // Returns the result of each app installed.
+    pub(crate) fn import_lockfile(&mut self, lockfile: &Lockfile) -> Vec<(String, XrdResult<()>)> {
+        let mut install_results: Vec<(String, XrdResult<()>)> = vec![];
+
+        for (app_name, locked_app) in &lockfile.apps {
+            let install_result = self.install_locked_app(app_name, locked_app);
+            install_results.push((app_name.to_string(), install_result));
+        }
+
+        for app in self.config.apps.values_mut() {
+            app.enabled = lockfile.apps.contains_key(&app.get_app_name());
+        }
+        install_results
+    }
+
+    fn install_locked_app(&mut self, app_name: &String, locked_app: &LockedApp) -> XrdResult<()> {
+        // Apps missing from the list of mods (e.g. added manually to the settings of the other machine).
+        let app = self.config.apps.entry(app_name.to_string())
+            .or_insert(AppStruct::new(locked_app.repo_owner.to_string(), locked_app.repo_name.to_string(), locked_app.app_type.clone()))
+            .clone();
+        let expected_asset_hashes: HashMap<String, String> = locked_app.asset_hashes.clone().into_iter().collect();
+
+        // Assets from other platforms can't be compared.
+        let installed_asset_hashes = app.get_asset_hashes(&self.get_mod_dir_path(app_name)?)?;
+        let is_installed = app.tag_name == locked_app.tag_name && app.id == locked_app.id && !app.is_local_install()
+            && app.get_asset_names(&app.tag_name).iter()
+                .filter(|asset_name| expected_asset_hashes.contains_key(*asset_name))
+                .all(|asset_name| installed_asset_hashes.get(asset_name) == expected_asset_hashes.get(asset_name));
+        if is_installed {
+            return Ok(());
+        }
+
+        let tag_info = self.get_release(app_name, &locked_app.tag_name)?.tag_info;
+        if tag_info.id != locked_app.id {
+            return Err(XrdError::ReleaseChanged { app_name: app_name.to_string(), tag_name: locked_app.tag_name.to_string() });
+        }
+
+        self.install_app_release(app_name.to_string(), &tag_info, Some(&expected_asset_hashes))
+    }
+
+    pub(crate) fn update_all(&mut self) -> XrdResult<()> {
+        let tags_hashmap: HashMap<String, TagInfo> = self.get_latest_tags_hash_map()?;
         let mut new_verison_found_bool: bool = false;
 
         for (app_name,latest_tag_info) in &tags_hashmap {
@@ -179,7 +634,9 @@ impl Manager {
                 Ok(true) => {
                     // Download
                     for (app_name,latest_tag_info) in &tags_hashmap {
-                        self.update_app(app_name.to_owned(), latest_tag_info);
+                        if let Err(e) = self.update_app(app_name.to_owned(), latest_tag_info) {
+                            println!("Error updating app '{}': '{}'", app_name, e)
+                        }
                     }
 
                     match self.save_config(){
@@ -208,7 +665,12 @@ impl Manager {
 
         // Patch the apps
         for app_name in apps_to_patch_vec {
-            self.patch_app(app_name);
+            match self.patch_app(app_name.to_string()) {
+                Ok(patch_output) => {
+                    println!("{}", patch_output.get_formated_log());
+                }
+                Err(e) => {println!("Error when patching app '{}' '{e}'", app_name)}
+            }
         }
 
         // Post patch save
@@ -220,6 +682,7 @@ impl Manager {
                 println!("Error Saving the configuration: '{}'",e)
             }
         }
+        Ok(())
     }
 
     pub (crate) fn get_app_names(&self) -> Vec<String> {
@@ -253,4 +716,16 @@ impl Manager {
     //
     //     app_list
     // }
-}
\ No newline at end of file
+}
+
+// Removes the files the app copied to the game folder when patching.
+fn remove_copied_patch_files(xrd_game_folder: &String, app: &AppStruct) -> XrdResult<()> {
+    let (files_to_copy, _) = app.get_patch_files();
+    for filename in files_to_copy {
+        let copied_file_path = format!("{}/Binaries/Win32/{}", xrd_game_folder, filename);
+        if Path::new(&copied_file_path).exists() {
+            fs::remove_file(&copied_file_path)?;
+        }
+    }
+    Ok(())
+}
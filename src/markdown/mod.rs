@@ -0,0 +1,230 @@
+// Markdown to ratatui lines, for the release notes.
+// Covers what GitHub release bodies usually contain: headings, lists, emphasis, code, links, quotes, rules and tables.
+// Raw HTML is skipped.
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use crate::theme::{get_theme, Theme};
+
+#[derive(Default)]
+struct MarkdownStyles {
+    heading: Style,
+    code: Style,
+    link: Style,
+    quote: Style,
+    marker: Style,
+}
+
+impl From<Theme> for MarkdownStyles {
+    fn from(theme: Theme) -> Self {
+        MarkdownStyles {
+            heading: Style::new().fg(theme.heading).add_modifier(Modifier::BOLD),
+            code: Style::new().fg(theme.code).bg(theme.code_bg),
+            link: Style::new().fg(theme.link).add_modifier(Modifier::UNDERLINED),
+            quote: Style::new().fg(theme.quote).add_modifier(Modifier::ITALIC),
+            marker: Style::new().fg(theme.marker),
+        }
+    }
+}
+
+#[derive(Default)]
+struct MarkdownRenderer {
+    styles: MarkdownStyles,
+    lines: Vec<Line<'static>>,
+    current_spans: Vec<Span<'static>>,
+    style_stack: Vec<Style>,
+    // Next number of each nested list, None for bullet lists.
+    list_stack: Vec<Option<u64>>,
+    // Marker to place on the first line of the current list item, e.g. "• " or "3. ".
+    item_marker: Option<String>,
+    item_marker_width: usize,
+    quote_depth: usize,
+    // Text of the code block being read, rendered once it ends.
+    code_block: Option<String>,
+}
+
+impl MarkdownRenderer {
+    fn get_current_style(&self) -> Style {
+        self.style_stack.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.style_stack.push(self.get_current_style().patch(style));
+    }
+
+    fn push_text(&mut self, text: String, style: Style) {
+        self.current_spans.push(Span::styled(text, style));
+    }
+
+    // Quote bars and list indentation, followed by the item marker on the first line of an item.
+    fn get_line_prefix(&mut self) -> Vec<Span<'static>> {
+        let mut prefix: Vec<Span<'static>> = vec![];
+        if self.quote_depth > 0 {
+            prefix.push(Span::styled("│ ".repeat(self.quote_depth), self.styles.quote));
+        }
+        if !self.list_stack.is_empty() {
+            prefix.push(Span::raw("  ".repeat(self.list_stack.len() - 1)));
+            match self.item_marker.take() {
+                Some(item_marker) => prefix.push(Span::styled(item_marker, self.styles.marker)),
+                None => prefix.push(Span::raw(" ".repeat(self.item_marker_width))),
+            }
+        }
+        prefix
+    }
+
+    fn flush_line(&mut self) {
+        if self.current_spans.is_empty() && self.item_marker.is_none() {
+            return;
+        }
+        let mut spans = self.get_line_prefix();
+        spans.append(&mut self.current_spans);
+        self.lines.push(Line::from(spans));
+    }
+
+    // Blank line between blocks, unless inside a list, which are rendered compact.
+    fn end_block(&mut self) {
+        self.flush_line();
+        let last_line_is_blank = self.lines.last().is_none_or(|line| line.spans.is_empty());
+        if self.list_stack.is_empty() && !last_line_is_blank {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn push_code_block(&mut self, code: &str) {
+        for code_line in code.trim_end_matches('\n').split('\n') {
+            let mut spans = self.get_line_prefix();
+            spans.push(Span::styled(format!("  {code_line}"), self.styles.code));
+            self.lines.push(Line::from(spans));
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush_line();
+                let heading_style = match level {
+                    HeadingLevel::H1 => self.styles.heading.add_modifier(Modifier::UNDERLINED),
+                    HeadingLevel::H2 => self.styles.heading,
+                    _ => self.styles.heading.add_modifier(Modifier::ITALIC),
+                };
+                self.push_style(heading_style);
+            }
+            Tag::BlockQuote(_) => {
+                self.flush_line();
+                self.quote_depth += 1;
+                self.push_style(self.styles.quote);
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush_line();
+                self.code_block = Some(String::new());
+                if let CodeBlockKind::Fenced(language) = kind && !language.is_empty() {
+                    let mut spans = self.get_line_prefix();
+                    spans.push(Span::styled(format!("  {language}"), self.styles.quote));
+                    self.lines.push(Line::from(spans));
+                }
+            }
+            Tag::List(first_number) => {
+                self.flush_line();
+                self.list_stack.push(first_number);
+            }
+            Tag::Item => {
+                self.flush_line();
+                let item_marker = match self.list_stack.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.item_marker_width = item_marker.chars().count();
+                self.item_marker = Some(item_marker);
+            }
+            Tag::Emphasis => self.push_style(Style::new().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::new().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self.push_style(Style::new().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { .. } | Tag::Image { .. } => self.push_style(self.styles.link),
+            Tag::Table(_) => self.flush_line(),
+            Tag::TableCell if !self.current_spans.is_empty() => self.push_text(" │ ".to_string(), self.styles.marker),
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_block(),
+            TagEnd::Heading(_) => {
+                self.style_stack.pop();
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_line();
+                self.style_stack.pop();
+                self.quote_depth -= 1;
+                if self.quote_depth == 0 {
+                    self.end_block();
+                }
+            }
+            TagEnd::CodeBlock => {
+                if let Some(code) = self.code_block.take() {
+                    self.push_code_block(&code);
+                }
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.flush_line();
+                self.list_stack.pop();
+                if self.list_stack.is_empty() {
+                    self.end_block();
+                }
+            }
+            TagEnd::Item => self.flush_line(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link | TagEnd::Image => {
+                self.style_stack.pop();
+            }
+            TagEnd::TableHead | TagEnd::TableRow => self.flush_line(),
+            TagEnd::Table => self.end_block(),
+            _ => {}
+        }
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag) => self.end_tag(tag),
+            Event::Text(text) => match self.code_block.as_mut() {
+                Some(code) => code.push_str(&text),
+                None => self.push_text(text.to_string(), self.get_current_style()),
+            },
+            Event::Code(code) => self.push_text(code.to_string(), self.get_current_style().patch(self.styles.code)),
+            Event::SoftBreak => self.push_text(" ".to_string(), self.get_current_style()),
+            Event::HardBreak => self.flush_line(),
+            Event::Rule => {
+                self.flush_line();
+                self.lines.push(Line::styled("─".repeat(20), self.styles.marker));
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => {
+                let task_marker = if checked { "[x] " } else { "[ ] " };
+                self.push_text(task_marker.to_string(), self.styles.marker);
+            }
+            // Html, footnotes, math...
+            _ => {}
+        }
+    }
+}
+
+pub fn markdown_to_lines(markdown: &str) -> Vec<Line<'static>> {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS;
+    let mut renderer = MarkdownRenderer { styles: MarkdownStyles::from(get_theme()), ..MarkdownRenderer::default() };
+    // GitHub bodies use CRLF
+    for event in Parser::new_ext(&markdown.replace('\r', ""), options) {
+        renderer.handle_event(event);
+    }
+    renderer.flush_line();
+
+    while renderer.lines.last().is_some_and(|line| line.spans.is_empty()) {
+        renderer.lines.pop();
+    }
+    renderer.lines
+}
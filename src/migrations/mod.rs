@@ -0,0 +1,156 @@
+// db.json schema migrations.
+// The raw JSON is upgraded one version at a time, before deserializing it into `Config`.
+//
+// To change the format:
+//  - Bump CURRENT_SCHEMA_VERSION.
+//  - Add a function upgrading the previous version to the new one to MIGRATIONS.
+
+use serde_json::{Map, Value};
+use crate::error::{XrdError, XrdResult};
+use crate::stuff::DEFAULT_PROFILE_NAME;
+
+pub const CURRENT_SCHEMA_VERSION: u64 = 2;
+
+type Migration = fn(&mut Map<String, Value>) -> XrdResult<()>;
+
+// Index N upgrades from version N to N+1.
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+];
+
+// Files written before schema_version existed are version 0.
+pub fn get_schema_version(raw_config: &Value) -> u64 {
+    raw_config.get("schema_version").and_then(|version| version.as_u64()).unwrap_or(0)
+}
+
+// Returns the upgraded config. Files written by a newer version of the tool are refused.
+pub fn migrate_config(mut raw_config: Value) -> XrdResult<Value> {
+    let found_version = get_schema_version(&raw_config);
+
+    if found_version > CURRENT_SCHEMA_VERSION {
+        return Err(XrdError::ConfigTooNew { found_version, supported_version: CURRENT_SCHEMA_VERSION });
+    }
+
+    let config_object = match raw_config.as_object_mut() {
+        Some(config_object) => config_object,
+        None => return Err(XrdError::Other("db.json doesn't contain a JSON object".to_string())),
+    };
+
+    for version in found_version..CURRENT_SCHEMA_VERSION {
+        MIGRATIONS[version as usize](config_object)?;
+        config_object.insert("schema_version".to_string(), Value::from(version + 1));
+    }
+
+    Ok(raw_config)
+}
+
+// v0 -> v1
+// Hand edited apps could miss the repo owner/name, these are taken from the app name ("owner/name").
+fn migrate_v0_to_v1(config: &mut Map<String, Value>) -> XrdResult<()> {
+    if let Some(Value::Object(apps)) = config.get_mut("apps") {
+        for (app_name, app) in apps.iter_mut() {
+            let Some(app) = app.as_object_mut() else { continue };
+            let Some((repo_owner, repo_name)) = app_name.split_once('/') else { continue };
+
+            if !app.contains_key("repo_owner") {
+                app.insert("repo_owner".to_string(), Value::from(repo_owner));
+            }
+            if !app.contains_key("repo_name") {
+                app.insert("repo_name".to_string(), Value::from(repo_name));
+            }
+        }
+    }
+    Ok(())
+}
+
+// v1 -> v2
+// Profiles were added, the current setup becomes the default profile.
+fn migrate_v1_to_v2(config: &mut Map<String, Value>) -> XrdResult<()> {
+    let mut enabled_apps: Vec<String> = vec![];
+    let mut patched_apps: Vec<String> = vec![];
+
+    if let Some(Value::Object(apps)) = config.get("apps") {
+        for (app_name, app) in apps {
+            if app.get("enabled").and_then(|enabled| enabled.as_bool()).unwrap_or(false) {
+                enabled_apps.push(app_name.to_string());
+            }
+            if app.get("patched").and_then(|patched| patched.as_bool()).unwrap_or(false) {
+                patched_apps.push(app_name.to_string());
+            }
+        }
+    }
+
+    let mut profiles = Map::new();
+    profiles.insert(DEFAULT_PROFILE_NAME.to_string(), serde_json::json!({
+        "enabled_apps": enabled_apps,
+        "patched_apps": patched_apps,
+    }));
+    config.insert("profiles".to_string(), Value::Object(profiles));
+    config.insert("active_profile".to_string(), Value::from(DEFAULT_PROFILE_NAME));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::stuff::Config;
+    use super::*;
+
+    fn get_v0_config() -> Value {
+        json!({
+            "apps": {
+                "kkots/ggxrd_hitbox_overlay_2211": { "enabled": true, "patched": true },
+                "Iquis/rev2-wakeup-tool": { "repo_owner": "Iquis", "repo_name": "rev2-wakeup-tool", "enabled": true },
+                "kkots/GGXrdMirrorColorSelect": { "enabled": false, "patched": false },
+            }
+        })
+    }
+
+    #[test]
+    fn upgrades_v0_to_the_current_version() {
+        let config = migrate_config(get_v0_config()).unwrap();
+        assert_eq!(get_schema_version(&config), CURRENT_SCHEMA_VERSION);
+
+        let hitbox_overlay = &config["apps"]["kkots/ggxrd_hitbox_overlay_2211"];
+        assert_eq!(hitbox_overlay["repo_owner"], "kkots");
+        assert_eq!(hitbox_overlay["repo_name"], "ggxrd_hitbox_overlay_2211");
+        assert_eq!(config["apps"]["Iquis/rev2-wakeup-tool"]["repo_owner"], "Iquis");
+        // Still readable as a Config
+        assert!(serde_json::from_value::<Config>(config).is_ok());
+    }
+
+    #[test]
+    fn default_profile_has_the_enabled_and_patched_apps() {
+        let config = migrate_config(get_v0_config()).unwrap();
+        assert_eq!(config["active_profile"], DEFAULT_PROFILE_NAME);
+
+        let default_profile = &config["profiles"][DEFAULT_PROFILE_NAME];
+        let mut enabled_apps: Vec<&str> = default_profile["enabled_apps"].as_array().unwrap().iter().filter_map(Value::as_str).collect();
+        enabled_apps.sort();
+        assert_eq!(enabled_apps, vec!["Iquis/rev2-wakeup-tool", "kkots/ggxrd_hitbox_overlay_2211"]);
+        assert_eq!(default_profile["patched_apps"], json!(["kkots/ggxrd_hitbox_overlay_2211"]));
+    }
+
+    #[test]
+    fn current_version_is_left_unchanged() {
+        let config = json!({ "schema_version": CURRENT_SCHEMA_VERSION, "apps": {}, "active_profile": "custom" });
+        assert_eq!(migrate_config(config.clone()).unwrap(), config);
+    }
+
+    #[test]
+    fn refuses_newer_versions() {
+        let config = json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1, "apps": {} });
+        assert!(matches!(
+            migrate_config(config),
+            Err(XrdError::ConfigTooNew { found_version, supported_version }) if found_version == CURRENT_SCHEMA_VERSION + 1 && supported_version == CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    #[test]
+    fn refuses_values_that_arent_objects() {
+        for config in [json!([]), json!("apps"), json!(null), json!(2)] {
+            assert!(migrate_config(config).is_err());
+        }
+    }
+}
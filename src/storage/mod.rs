@@ -0,0 +1,219 @@
+// Where the tool stores its files.
+//
+// Portable mode (today's behavior), everything next to the executable (or in XRD_MOD_FOLDER):
+//   db.json, <owner>/<repo> mod folders, cache
+//
+// Otherwise, the XDG base directories are followed:
+//   $XDG_CONFIG_HOME/xrd_mods_downloader/settings.json   user preferences
+//   $XDG_STATE_HOME/xrd_mods_downloader/state.json       installed versions, patches applied...
+//   $XDG_DATA_HOME/xrd_mods_downloader/<owner>/<repo>    downloaded mods
+//   $XDG_CACHE_HOME/xrd_mods_downloader                  releases and assets fetched, see the cache module
+
+use std::env;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+use crate::error::{XrdError, XrdResult};
+use crate::migrations::get_schema_version;
+
+pub const APP_DIR_NAME: &str = "xrd_mods_downloader";
+// Placing a file with this name next to the executable enables the portable mode.
+pub const PORTABLE_MARKER_FILE_NAME: &str = "portable";
+
+// App fields that describe the machine state rather than user preferences, stored in state.json.
+const STATE_APP_FIELDS: [&str; 7] = ["id", "tag_name", "published_at", "url_source_version", "local_source", "patched", "tracked"];
+
+#[derive(Debug, Clone)]
+pub struct StoragePaths {
+    pub(crate) portable: bool,
+    pub(crate) settings_file: String,
+    // Same as settings_file in portable mode.
+    pub(crate) state_file: String,
+    pub(crate) mods_dir: String,
+    pub(crate) cache_dir: String,
+}
+
+impl StoragePaths {
+    pub(crate) fn get_mod_dir_path(&self, app_name: &String) -> String {
+        format!("{}/{}", self.mods_dir, app_name)
+    }
+
+    // Copy of the unpatched GuiltyGearXrd.exe
+    pub(crate) fn get_game_backup_file_path(&self) -> String {
+        format!("{}/backups/GuiltyGearXrd.exe", self.mods_dir)
+    }
+
+    // Hashes of the backup and of the patched executable, see GameBackupInfo.
+    pub(crate) fn get_game_backup_info_file_path(&self) -> String {
+        format!("{}/backups/GuiltyGearXrd.exe.json", self.mods_dir)
+    }
+
+    // Result of the last update check, see the watch module.
+    pub(crate) fn get_update_status_file_path(&self) -> String {
+        match Path::new(&self.state_file).parent() {
+            Some(state_dir) => state_dir.join("update_status.json").to_string_lossy().to_string(),
+            None => "update_status.json".to_string(),
+        }
+    }
+
+    pub(crate) fn get_lock_file_path(&self) -> String {
+        format!("{}.lock", self.settings_file)
+    }
+
+    // Files to read/write, settings first.
+    pub(crate) fn get_config_files(&self) -> Vec<String> {
+        match self.portable {
+            true => vec![self.settings_file.to_string()],
+            false => vec![self.settings_file.to_string(), self.state_file.to_string()],
+        }
+    }
+}
+
+fn get_executable_dir_path() -> XrdResult<PathBuf> {
+    let exe_path = env::current_exe()?;
+    match exe_path.parent() {
+        Some(exe_dir) => Ok(exe_dir.to_path_buf()),
+        None => Err(XrdError::Other(format!("Couldn't get the folder of the executable '{}'", exe_path.display())))
+    }
+}
+
+fn get_portable_storage_paths(portable_dir: &Path) -> StoragePaths {
+    let db_file_path = portable_dir.join("db.json").to_string_lossy().to_string();
+    StoragePaths {
+        portable: true,
+        settings_file: db_file_path.to_string(),
+        state_file: db_file_path,
+        mods_dir: portable_dir.to_string_lossy().to_string(),
+        cache_dir: portable_dir.join("cache").to_string_lossy().to_string(),
+    }
+}
+
+pub fn get_storage_paths() -> XrdResult<StoragePaths> {
+    // Explicit folder
+    if let Ok(env_val) = env::var("XRD_MOD_FOLDER") {
+        return Ok(get_portable_storage_paths(Path::new(&env_val)));
+    }
+
+    // Portable install, either marked as such or with a db.json from older versions next to the executable.
+    let exe_dir = get_executable_dir_path()?;
+    if exe_dir.join(PORTABLE_MARKER_FILE_NAME).exists() || exe_dir.join("db.json").exists() {
+        return Ok(get_portable_storage_paths(&exe_dir));
+    }
+
+    // dirs follows the XDG env vars on Linux, and uses the known folders on Windows.
+    let (Some(config_dir), Some(data_dir)) = (dirs::config_dir(), dirs::data_dir()) else {
+        return Ok(get_portable_storage_paths(&exe_dir));
+    };
+    // There is no state folder on Windows
+    let state_dir = dirs::state_dir().or(dirs::data_local_dir()).unwrap_or(data_dir.to_path_buf());
+    let cache_dir = dirs::cache_dir().map_or(data_dir.join(APP_DIR_NAME).join("cache"), |cache_dir| cache_dir.join(APP_DIR_NAME));
+
+    Ok(StoragePaths {
+        portable: false,
+        settings_file: config_dir.join(APP_DIR_NAME).join("settings.json").to_string_lossy().to_string(),
+        state_file: state_dir.join(APP_DIR_NAME).join("state.json").to_string_lossy().to_string(),
+        mods_dir: data_dir.join(APP_DIR_NAME).to_string_lossy().to_string(),
+        cache_dir: cache_dir.to_string_lossy().to_string(),
+    })
+}
+
+// Splits the serialized config into the settings and state documents.
+// Both keep the schema version and the app names, so each one can be read on its own.
+pub fn split_config_value(config_value: Value) -> (Value, Value) {
+    let mut settings = match config_value {
+        Value::Object(settings) => settings,
+        _ => Map::new(),
+    };
+    let mut state = Map::new();
+
+    if let Some(schema_version) = settings.get("schema_version") {
+        state.insert("schema_version".to_string(), schema_version.clone());
+    }
+
+    let mut state_apps = Map::new();
+    if let Some(Value::Object(apps)) = settings.get_mut("apps") {
+        for (app_name, app) in apps.iter_mut() {
+            let Some(app) = app.as_object_mut() else { continue };
+            let mut state_app = Map::new();
+            for field in STATE_APP_FIELDS {
+                if let Some(value) = app.remove(field) {
+                    state_app.insert(field.to_string(), value);
+                }
+            }
+            state_apps.insert(app_name.to_string(), Value::Object(state_app));
+        }
+    }
+    state.insert("apps".to_string(), Value::Object(state_apps));
+
+    (Value::Object(settings), Value::Object(state))
+}
+
+// Inverse of split_config_value. Apps only present in the state are dropped, as their settings are gone.
+pub fn merge_config_values(settings: Value, state: Option<Value>) -> Value {
+    let Some(state) = state else { return settings };
+
+    // Older of both versions, so the migrations upgrade whatever is behind.
+    let schema_version = get_schema_version(&settings).min(get_schema_version(&state));
+
+    let mut merged = match settings {
+        Value::Object(settings) => settings,
+        _ => Map::new(),
+    };
+    merged.insert("schema_version".to_string(), Value::from(schema_version));
+
+    if let (Some(Value::Object(apps)), Some(Value::Object(state_apps))) = (merged.get_mut("apps"), state.get("apps")) {
+        for (app_name, app) in apps.iter_mut() {
+            let (Some(app), Some(Value::Object(state_app))) = (app.as_object_mut(), state_apps.get(app_name)) else { continue };
+            for (field, value) in state_app {
+                app.insert(field.to_string(), value.clone());
+            }
+        }
+    }
+
+    Value::Object(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stuff::{AppStruct, AppType, Config};
+    use super::*;
+
+    fn split_and_merge(config: &Config) -> Value {
+        let (settings, state) = split_config_value(serde_json::to_value(config).unwrap());
+        merge_config_values(settings, Some(state))
+    }
+
+    #[test]
+    fn default_config_survives_split_and_merge() {
+        let config = Config::default();
+        assert_eq!(split_and_merge(&config), serde_json::to_value(&config).unwrap());
+    }
+
+    #[test]
+    fn app_fields_survive_split_and_merge() {
+        let mut app = AppStruct::new("kkots".to_string(), "ggxrd_hitbox_overlay_2211".to_string(), AppType::HitboxOverlay);
+        app.enabled = true;
+        app.patched = true;
+        app.tag_name = "v1.0.0".to_string();
+        let mut config = Config::default();
+        config.apps.insert(app.get_app_name(), app);
+
+        assert_eq!(split_and_merge(&config), serde_json::to_value(&config).unwrap());
+    }
+
+    #[test]
+    fn state_fields_only_go_to_the_state() {
+        let mut config = Config::default();
+        let app = AppStruct::new("kkots".to_string(), "ggxrd_hitbox_overlay_2211".to_string(), AppType::HitboxOverlay);
+        config.apps.insert(app.get_app_name(), app);
+
+        let (settings, state) = split_config_value(serde_json::to_value(&config).unwrap());
+        let settings_app = &settings["apps"]["kkots/ggxrd_hitbox_overlay_2211"];
+        let state_app = &state["apps"]["kkots/ggxrd_hitbox_overlay_2211"];
+        for field in STATE_APP_FIELDS {
+            assert!(settings_app.get(field).is_none(), "'{field}' is in the settings");
+            assert!(state_app.get(field).is_some(), "'{field}' is missing from the state");
+        }
+        assert!(settings_app.get("enabled").is_some() && state_app.get("enabled").is_none());
+        assert_eq!(settings["schema_version"], state["schema_version"]);
+    }
+}
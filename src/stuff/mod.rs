@@ -4,7 +4,6 @@ use std::collections::HashMap;
 use std::fs::{File, create_dir, create_dir_all, Permissions};
 use std::io::{Error, Read, Seek, Write};
 use std::path::Path;
-use std::process::{exit, Stdio};
 use futures::future::{err, ok, SelectAll};
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -19,18 +18,18 @@ use std::ops::BitOr;
 use downloader::Verification::Failed;
 use futures::Stream;
 use std::process::Command;
-use crate::functions::{download_file_to_path, get_xrd_folder_from_file, unzip_file};
+use crate::cache::ReleaseCache;
+use crate::error::{XrdError, XrdResult};
+use crate::version::{compare_versions, VersionComparison};
+use crate::keybindings::KeybindingsConfig;
+use crate::functions::{copy_dir_contents, find_xrd_game_folder, get_exit_code_explanation, get_file_sha256, run_patcher, unzip_file, validate_xrd_game_folder};
 
 // Linux imports
 #[cfg(target_os = "linux")]
 use std::os::unix::fs::PermissionsExt;
 use strum::{Display, EnumIter, FromRepr};
-// Windows imports
-#[cfg(target_os = "windows")]
-// Get path from Windows registry
-use winreg::{RegKey,enums::*};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TagAssets {
     // url: String,
     id: i32,
@@ -42,7 +41,7 @@ pub struct TagAssets {
 }
 
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TagInfo {
     // url: String,
     pub(crate) html_url: String,
@@ -51,6 +50,8 @@ pub struct TagInfo {
     tarball_url: String,
     body: String,
     pub(crate) published_at: String,
+    #[serde(default)]
+    pub(crate) prerelease: bool,
     assets: Vec<TagAssets>
 }
 
@@ -60,7 +61,90 @@ impl TagInfo {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+// Prompt printed by a patcher, matched case-insensitively against any of the expected texts.
+#[derive(Debug, Clone)]
+pub struct PatcherPrompt {
+    pub(crate) description: String,
+    pub(crate) expected: Vec<String>,
+    pub(crate) answer: String,
+    // Optional prompts can be missing if the patcher exits before printing them.
+    pub(crate) optional: bool,
+}
+
+impl PatcherPrompt {
+    pub(crate) fn new(description: &str, expected: &[&str], answer: String) -> Self {
+        Self {
+            description: description.to_string(),
+            expected: expected.iter().map(|text| text.to_lowercase()).collect(),
+            answer,
+            optional: false,
+        }
+    }
+
+    pub(crate) fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    // Returns the position and length of the first expected text found in the (lowercase) output.
+    pub(crate) fn find_in(&self, output: &str) -> Option<(usize, usize)> {
+        self.expected.iter()
+            .filter_map(|text| output.find(text.as_str()).map(|position| (position, text.len())))
+            .min_by_key(|(position, _)| *position)
+    }
+}
+
+// Result of executing a mod's patcher.
+#[derive(Debug, Default, Clone)]
+pub struct PatchOutput {
+    pub(crate) app_name: String,
+    pub(crate) date: String,
+    pub(crate) executable: String,
+    pub(crate) exit_code: Option<i32>,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    pub(crate) copy_errors: Vec<String>,
+    // Set when the patcher didn't behave as expected (prompts changed, timed out...).
+    pub(crate) interaction_error: Option<String>,
+    // Set when no clean backup of GuiltyGearXrd.exe could be made before patching.
+    pub(crate) backup_warning: Option<String>,
+    pub(crate) log_file_path: String,
+}
+
+impl PatchOutput {
+    pub(crate) fn is_successful(&self) -> bool {
+        // Apps that only copy files have no executable, hence no exit code.
+        self.copy_errors.is_empty() && self.interaction_error.is_none() && (self.executable.is_empty() || self.exit_code == Some(0))
+    }
+
+    pub(crate) fn get_exit_code_explanation(&self) -> String {
+        match (&self.interaction_error, self.executable.is_empty()) {
+            (Some(interaction_error), _) => interaction_error.to_string(),
+            (None, true) => "No patcher executed.".to_string(),
+            (None, false) => get_exit_code_explanation(self.exit_code)
+        }
+    }
+
+    pub(crate) fn get_formated_log(&self) -> String {
+        let mut log = String::new();
+        let _ = writeln!(log, "App: {}", self.app_name);
+        let _ = writeln!(log, "Date: {}", self.date);
+        let _ = writeln!(log, "Executable: {}", self.executable);
+        match self.exit_code {
+            Some(code) => { let _ = writeln!(log, "Exit code: {code}"); }
+            None => { let _ = writeln!(log, "Exit code: -"); }
+        }
+        let _ = writeln!(log, "{}", self.get_exit_code_explanation());
+        for copy_error in &self.copy_errors {
+            let _ = writeln!(log, "{copy_error}");
+        }
+        let _ = writeln!(log, "==============\n=== Stdout ===\n==============\n{}", self.stdout.replace("\r", ""));
+        let _ = writeln!(log, "==============\n=== Stderr ===\n==============\n{}", self.stderr.replace("\r", ""));
+        log
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Display)]
 pub enum AppType {
     #[default]
     Unknown,
@@ -87,6 +171,9 @@ pub struct AppStruct {
     pub(crate) published_at: String,
     #[serde(default)]
     pub(crate) url_source_version: String,
+    // File or folder the installed version was taken from, empty when installed from a GitHub release.
+    #[serde(default)]
+    pub(crate) local_source: String,
     #[serde(default = "set_false")]
     pub(crate) automatically_patch: bool,
     #[serde(default = "set_false")]
@@ -123,6 +210,7 @@ impl AppStruct {
             published_at: "".to_string(),
             app_type: app_type,
             url_source_version: "".to_string(),
+            local_source: "".to_string(),
             automatically_patch: false,
             patched: false,
             enabled: false,
@@ -141,14 +229,15 @@ impl AppStruct {
         format!("https://api.github.com/repos/{}/{}",self.repo_owner,self.repo_name).to_string()
     }
 
-    pub(crate) fn download_mod(&self, destination_dir: &String, tag_info: &TagInfo) -> std::io::Result<()> {
+    // Release assets downloaded for this platform.
+    pub(crate) fn get_asset_names(&self, tag_name: &String) -> Vec<String> {
         let mut assets_whitelist:Vec<String> = vec![];
 
         match self.app_type {
             AppType::WakeupTool => {
                 assets_whitelist = vec![
-                    format!("GGXrdReversalTool.{}.zip",tag_info.tag_name), // Iquis
-                    format!("GGXrdReversalTool-{}.zip",tag_info.tag_name) // kkots
+                    format!("GGXrdReversalTool.{}.zip",tag_name), // Iquis
+                    format!("GGXrdReversalTool-{}.zip",tag_name) // kkots
                 ];
             }
             AppType::HitboxOverlay => {
@@ -183,6 +272,13 @@ impl AppStruct {
             AppType::Unknown | _ => {}
         }
 
+        assets_whitelist
+    }
+
+    // The assets are taken from the release cache, downloading them if missing.
+    // When expected_asset_hashes is given, the assets listed in it must match their hash before being extracted.
+    pub(crate) fn download_mod(&self, destination_dir: &String, tag_info: &TagInfo, expected_asset_hashes: Option<&HashMap<String, String>>, release_cache: &ReleaseCache) -> XrdResult<()> {
+        let assets_whitelist = self.get_asset_names(&tag_info.tag_name);
         let mut matched_assets_list: Vec<&TagAssets> = vec![];
 
         for asset in &tag_info.assets {
@@ -191,29 +287,149 @@ impl AppStruct {
             }
         }
 
+        let mut cached_asset_file_paths: Vec<String> = vec![];
         for matched_asset in &matched_assets_list {
-            download_file_to_path(matched_asset.browser_download_url.to_string(), destination_dir.to_string())
+            cached_asset_file_paths.push(release_cache.get_asset(tag_info, matched_asset)?);
         }
 
-        for matched_asset in matched_assets_list {
-            if matched_asset.name.ends_with(".zip") {
-                unzip_file(format!("{}/{}",destination_dir.to_string(),matched_asset.name),destination_dir.to_string());
+        if let Some(expected_asset_hashes) = expected_asset_hashes {
+            for (matched_asset, cached_asset_file_path) in matched_assets_list.iter().zip(&cached_asset_file_paths) {
+                if let Some(expected_hash) = expected_asset_hashes.get(&matched_asset.name) {
+                    let found_hash = get_file_sha256(cached_asset_file_path)?;
+                    if found_hash != *expected_hash {
+                        fs::remove_file(cached_asset_file_path)?;
+                        return Err(XrdError::AssetHashMismatch { asset_name: matched_asset.name.to_string(), expected: expected_hash.to_string(), found: found_hash });
+                    }
+                }
+            }
+        }
+
+        let asset_files: Vec<(String, String)> = matched_assets_list.iter()
+            .map(|matched_asset| matched_asset.name.to_string())
+            .zip(cached_asset_file_paths)
+            .collect();
+        self.extract_assets(destination_dir, &asset_files)
+    }
+
+    // Copies each (asset name, file path) to the mod folder under the asset name, extracting the zips.
+    fn extract_assets(&self, destination_dir: &String, asset_files: &[(String, String)]) -> XrdResult<()> {
+        for (asset_name, asset_file_path) in asset_files {
+            fs::copy(asset_file_path, format!("{}/{}", destination_dir, asset_name))?;
+        }
+
+        for (asset_name, _) in asset_files {
+            if asset_name.ends_with(".zip") {
+                unzip_file(format!("{destination_dir}/{asset_name}"), destination_dir.to_string())?;
             }
         }
 
-        // Todo raise error
         Ok(())
     }
 
-    pub(crate) fn patch_app(&self, xrd_game_folder: String, downloaded_mod_folder: &String) -> io::Result<()> {
-        // This assumes that only Linux or Windows will reach this point.
-        let xrd_binaries_folder_path = format!("{}/Binaries/Win32", xrd_game_folder);
+    // Installs a build that isn't published as a GitHub release: a zip, an executable, or a folder with the mod files.
+    pub(crate) fn install_local_mod(&self, destination_dir: &String, source_path: &String) -> XrdResult<()> {
+        let source = Path::new(source_path);
+        if source.is_dir() {
+            return copy_dir_contents(source_path, destination_dir);
+        }
+        if !source.is_file() {
+            return Err(XrdError::Other(format!("'{source_path}' doesn't exist")));
+        }
+
+        let file_name = source.file_name().map(|file_name| file_name.to_string_lossy().to_string()).unwrap_or_default();
+        // A renamed executable (e.g. "GGXrdFasterLoadingTimes (1).exe") is stored under the name the patch expects.
+        let executable_asset_names: Vec<String> = self.get_asset_names(&self.tag_name).into_iter()
+            .filter(|asset_name| !asset_name.ends_with(".zip"))
+            .collect();
+        let asset_name = match (file_name.ends_with(".zip"), executable_asset_names.as_slice()) {
+            (false, [executable_asset_name]) => executable_asset_name.to_string(),
+            _ => file_name,
+        };
+
+        self.extract_assets(destination_dir, &[(asset_name, source_path.to_string())])
+    }
+
+    pub(crate) fn is_local_install(&self) -> bool {
+        !self.local_source.is_empty()
+    }
+
+    // How the given release compares against the installed one.
+    pub(crate) fn compare_version(&self, release: &TagInfo) -> VersionComparison {
+        compare_versions(&self.tag_name, &self.published_at, &release.tag_name, &release.published_at)
+    }
+
+    // Local builds are never reported, as they aren't updated.
+    pub(crate) fn has_new_version(&self, latest: &TagInfo) -> bool {
+        !self.is_local_install() && self.compare_version(latest) == VersionComparison::Newer
+    }
+
+    // Releases after the installed one, up to the latest, newest first.
+    // Only the latest if the installed release isn't known (local builds, not installed yet...).
+    pub(crate) fn get_releases_since_installed(&self, releases: &[TagInfo], latest: &TagInfo) -> Vec<TagInfo> {
+        if !self.has_new_version(latest) {
+            return vec![latest.clone()];
+        }
+        let parse_date = |published_at: &String| chrono::DateTime::parse_from_rfc3339(published_at).ok();
+        let (Some(installed_published_at), Some(latest_published_at)) = (parse_date(&self.published_at), parse_date(&latest.published_at)) else {
+            return vec![latest.clone()];
+        };
+
+        let mut releases_since_installed: Vec<TagInfo> = releases.iter()
+            .filter(|release| release.tag_name != self.tag_name)
+            .filter(|release| parse_date(&release.published_at).is_some_and(|published_at| installed_published_at < published_at && published_at <= latest_published_at))
+            .cloned()
+            .collect();
+        releases_since_installed.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+
+        // The list only holds the most recent releases.
+        if !releases_since_installed.iter().any(|release| release.tag_name == latest.tag_name) {
+            releases_since_installed.insert(0, latest.clone());
+        }
+        releases_since_installed
+    }
+
+    // Hashes of the downloaded assets present in the mod folder.
+    pub(crate) fn get_asset_hashes(&self, mod_dir: &String) -> XrdResult<HashMap<String, String>> {
+        let mut asset_hashes: HashMap<String, String> = HashMap::new();
+        for asset_name in self.get_asset_names(&self.tag_name) {
+            let asset_file_path = format!("{}/{}", mod_dir, asset_name);
+            if Path::new(&asset_file_path).is_file() {
+                asset_hashes.insert(asset_name, get_file_sha256(&asset_file_path)?);
+            }
+        }
+        Ok(asset_hashes)
+    }
+
+    // Prompts expected from the patcher, in order, with the answer to send to each of them.
+    fn get_patcher_prompts(&self, xrd_binaries_folder_path: &String) -> Vec<PatcherPrompt> {
+        let mut prompts: Vec<PatcherPrompt> = vec![];
+
+        // Windows patchers don't require stdin.
+        if cfg!(unix) {
+            match self.app_type {
+                AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::BackgroundGamepad => {
+                    prompts = vec![
+                        PatcherPrompt::new("press enter to start", &["press enter", "press any key"], "\n".to_string()),
+                        PatcherPrompt::new("path to GuiltyGearXrd.exe", &["path to the file GuiltyGearXrd.exe", "path to GuiltyGearXrd.exe"], format!("{xrd_binaries_folder_path}/GuiltyGearXrd.exe\n")),
+                        PatcherPrompt::new("press enter to exit", &["press enter", "press any key"], "\n".to_string()).optional(),
+                    ];
+                }
+                // Some apps might not require stdin
+                _ => {}
+            }
+        }
+        prompts
+    }
+
+    pub(crate) fn is_patchable(&self) -> bool {
+        matches!(self.app_type, AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::BackgroundGamepad)
+    }
+
+    // Files copied to the game binaries folder, and the patcher to execute.
+    pub(crate) fn get_patch_files(&self) -> (Vec<String>, String) {
         let mut files_to_copy:Vec<String> = vec![]; // files to only copy
         let mut file_to_execute:String = String::new(); // file to execute. Copy skipped
-        let mut successful_patch =false;
-        let mut executable_filepath: String = String::new();
 
-        // prepare patch
         match self.app_type {
             AppType::HitboxOverlay => {
                 files_to_copy = vec![
@@ -239,20 +455,31 @@ impl AppStruct {
             AppType::Unknown | _ => {}
         }
 
+        (files_to_copy, file_to_execute)
+    }
+
+    pub(crate) fn patch_app(&self, xrd_game_folder: String, downloaded_mod_folder: &String) -> XrdResult<PatchOutput> {
+        // This assumes that only Linux or Windows will reach this point.
+        let xrd_binaries_folder_path = format!("{}/Binaries/Win32", xrd_game_folder);
+        let (files_to_copy, file_to_execute) = self.get_patch_files();
+        let mut patch_output = PatchOutput {
+            app_name: self.get_app_name(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            ..PatchOutput::default()
+        };
+
         for filename in files_to_copy {
             // Copy from local_mod_folder to xrd_game_folder
             let source_file_path = format!("{}/{}", downloaded_mod_folder, filename);
             let destination_new_file_path = format!("{}/{}", xrd_binaries_folder_path, filename);
-            match fs::copy(source_file_path.to_string(), destination_new_file_path.to_string()) {
-                Ok(_) => {}
-                Err(e) => {
-                    println!("Error copying '{}' -> '{}' <{e}>.", source_file_path,destination_new_file_path);
-                }
+            if let Err(e) = fs::copy(&source_file_path, &destination_new_file_path) {
+                patch_output.copy_errors.push(format!("Error copying '{}' -> '{}' <{e}>.", source_file_path, destination_new_file_path));
             }
         }
 
         if !file_to_execute.is_empty() {
-            executable_filepath = format!("{}/{}", downloaded_mod_folder, file_to_execute);
+            let executable_filepath = format!("{}/{}", downloaded_mod_folder, file_to_execute);
+            patch_output.executable = executable_filepath.to_string();
 
             // set chmod +x permissions (linux)
             #[cfg(target_os = "linux")]
@@ -262,73 +489,46 @@ impl AppStruct {
             }
 
             // Call command
-            println!("Executing {}",executable_filepath);
-
-            let mut command = Command::new(executable_filepath.to_string());
-            let mut stdin_input: String = String::new();
-            let mut child = command.stdin(Stdio::piped())
-                .spawn()?;
-            let mut stdin_pipe = child.stdin.take().unwrap();
-
-            // Stdin
-            if cfg!(unix) {
-
-                stdin_input = String::new();
-
-                // Stdin (Custom per app)
-                match self.app_type {
-                    AppType::HitboxOverlay | AppType::FasterLoadingTimes | AppType::BackgroundGamepad => {
-                        stdin_input =format!("\n{xrd_binaries_folder_path}/GuiltyGearXrd.exe\n\n");
-                        stdin_pipe.write_all(stdin_input.as_bytes()).unwrap();
-                    }
-                    AppType::Unknown => {
-                        println!("App {} of type {:?} doesn't support patching on Linux",self.get_app_name(),self.app_type);
-                    }
-                    // Some apps might not require stdin
-                    _ => {}
-                }
-            }
-
-            // Stdout
-            println!("==============\n=== Stdout ===\n==============");
-            let mut child_wait = child.wait_with_output();
-
-            // Check status
-            println!("==============\n=== Stderr ===\n==============");
-            let child_wait_unwrap = child_wait.unwrap();
+            run_patcher(&executable_filepath, &self.get_patcher_prompts(&xrd_binaries_folder_path), &mut patch_output)?;
+        }
 
-            match child_wait_unwrap.status.code() {
-                Some(0) => {
-                    successful_patch = true;
-                }
+        // Store the log next to the mod files.
+        let log_file_path = format!("{}/patch_{}.log", downloaded_mod_folder, chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        fs::write(&log_file_path, patch_output.get_formated_log())?;
+        patch_output.log_file_path = log_file_path;
 
-                Some(-1073741701) => { //x86
-                    println!("Exit code '{}'. Some DLLs might be missing.\nRefer to here to install the Latest Microsoft Visual C++ Redistributable Version https://learn.microsoft.com/en-us/cpp/windows/latest-supported-vc-redist?view=msvc-170#latest-microsoft-visual-c-redistributable-version",child_wait_unwrap.status.code().unwrap(),)
-                }
-                Some(-1073741515) => { //x64
-                    println!("Exit code '{}'. Some 64bit DLLs might be missing.\nRefer to here to install the Latest Microsoft Visual C++ Redistributable Version https://learn.microsoft.com/en-us/cpp/windows/latest-supported-vc-redist?view=msvc-170#latest-microsoft-visual-c-redistributable-version",child_wait_unwrap.status.code().unwrap(),)
-                }
-                unknown_code => {
-                    println!("Exit code '{}'. Ensure that the mod's executables can be manually executed. Maybe DLL are missing, for 32/86bits and/or 64bits https://learn.microsoft.com/en-us/cpp/windows/latest-supported-vc-redist?view=msvc-170#latest-microsoft-visual-c-redistributable-version",unknown_code.unwrap())
-                }
-            }
-        }
-        else { println!("App '{}' with type '{:?}' has no executable files declared, skipping patch",self.get_app_name(), self.app_type); }
-        if successful_patch {
-            Ok(())
-        } else {
-            panic!("Error while executing {}.",executable_filepath)
-        }
+        Ok(patch_output)
     }
 
     #[tokio::main]
-    pub(crate) async fn get_latest_tag(&self) -> color_eyre::Result<TagInfo, reqwest::Error> {
+    pub(crate) async fn get_latest_tag(&self) -> XrdResult<TagInfo> {
         // ➜  ~ curl -L \
         // -H "Accept: application/vnd.github+json" \
         // -H "X-GitHub-Api-Version: 2022-11-28" \
         // https://api.github.com/repos/kkots/ggxrd_hitbox_overlay_2211/releases/latest
 
         let repo_url_latest: String = format!("{}/releases/latest",self.get_api_repo_url());
+        self.get_release(repo_url_latest).await
+    }
+
+    #[tokio::main]
+    pub(crate) async fn get_tag(&self, tag_name: &String) -> XrdResult<TagInfo> {
+        let repo_url_tag: String = format!("{}/releases/tags/{}",self.get_api_repo_url(),tag_name);
+        self.get_release(repo_url_tag).await
+    }
+
+    // Most recent releases, newest first.
+    #[tokio::main]
+    pub(crate) async fn get_releases(&self) -> XrdResult<Vec<TagInfo>> {
+        let repo_url_releases: String = format!("{}/releases?per_page=100",self.get_api_repo_url());
+        self.get_github_json(repo_url_releases).await
+    }
+
+    async fn get_release(&self, release_url: String) -> XrdResult<TagInfo> {
+        self.get_github_json(release_url).await
+    }
+
+    async fn get_github_json<T: serde::de::DeserializeOwned>(&self, release_url: String) -> XrdResult<T> {
 
         let mut headers = reqwest::header::HeaderMap::new();
 
@@ -336,30 +536,18 @@ impl AppStruct {
         headers.insert("GitHub-Api-Version","2022-11-28".parse().unwrap());
 
         // releases/latest
-        let client = reqwest::Client::builder().user_agent("Script-Check-Xrd-Tools").build();
-        let response = client.unwrap().get(&repo_url_latest).headers(headers).send().await?;
+        let client = reqwest::Client::builder().user_agent("Script-Check-Xrd-Tools").build()?;
+        let response = client.get(&release_url).headers(headers).send().await?;
         let response_status = response.status();
-        let mut tag_info: TagInfo = TagInfo {
-            html_url: "".to_string(),
-            id: 0,
-            tag_name: "".to_string(),
-            tarball_url: "".to_string(),
-            body: "".to_string(),
-            published_at: "".to_string(),
-            assets: vec![],
-        };
 
         match response_status {
             reqwest::StatusCode::OK => {
-                // println!("{:#?}",&response.json().await.unwrap());
-                tag_info = response.json().await.unwrap();
+                Ok(response.json().await?)
             }
             other => {
-                // println!("Unknown error. Status code {} when getting the latest tag for the repository {}",other, self.get_api_repo_url());
+                Err(XrdError::GithubStatus { url: release_url, status: other })
             }
         }
-
-        Ok(tag_info)
     }
 
 }
@@ -367,15 +555,74 @@ impl AppStruct {
 
 
 
+// Named set of mods, such as a tournament setup without overlays, or a lab setup.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct Profile {
+    #[serde(default)]
+    pub(crate) enabled_apps: Vec<String>,
+    #[serde(default)]
+    pub(crate) patched_apps: Vec<String>,
+}
+
+pub const DEFAULT_PROFILE_NAME: &str = "default";
+
+// tag_name of the apps installed from a local file or folder.
+pub const LOCAL_TAG_NAME: &str = "local";
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Config {
+    // Set to CURRENT_SCHEMA_VERSION when saved, see the migrations module.
+    #[serde(default)]
+    pub(crate) schema_version: u64,
     #[serde(default)]
     pub(crate) apps: HashMap<String,AppStruct>,
     #[serde(default)]
-    pub(crate) xrd_game_folder: String
+    pub(crate) xrd_game_folder: String,
+    // Releases and assets are only taken from the cache.
+    #[serde(default)]
+    pub(crate) offline_mode: bool,
+    #[serde(default)]
+    pub(crate) profiles: HashMap<String, Profile>,
+    // Kept in sync with the apps enabled/patched, see sync_active_profile.
+    #[serde(default)]
+    pub(crate) active_profile: String,
+    // Keys of the TUI, see the keybindings module.
+    #[serde(default)]
+    pub(crate) keybindings: KeybindingsConfig,
+    // Built-in theme name or path to a theme file, see the theme module.
+    #[serde(default)]
+    pub(crate) theme: String,
 }
 
 impl Config {
+    // Profile matching the apps currently enabled/patched.
+    pub(crate) fn get_current_profile(&self) -> Profile {
+        let mut profile = Profile::default();
+        for app in self.apps.values() {
+            if app.enabled {
+                profile.enabled_apps.push(app.get_app_name());
+            }
+            if app.patched {
+                profile.patched_apps.push(app.get_app_name());
+            }
+        }
+        profile.enabled_apps.sort();
+        profile.patched_apps.sort();
+        profile
+    }
+
+    pub(crate) fn sync_active_profile(&mut self) {
+        if !self.active_profile.is_empty() {
+            self.profiles.insert(self.active_profile.to_string(), self.get_current_profile());
+        }
+    }
+
+    pub(crate) fn get_profile_names(&self) -> Vec<String> {
+        let mut profile_names: Vec<String> = self.profiles.keys().cloned().collect();
+        profile_names.sort();
+        profile_names
+    }
+
     pub(crate) fn set_default_apps (&mut self) {
         let mut new_app_hashmap: HashMap<String,AppStruct> = HashMap::new();
         let mut holder_apps_vector: Vec<AppStruct> = vec![];
@@ -407,62 +654,27 @@ impl Config {
         }
 
         self.apps = new_app_hashmap;
-    }
 
-    pub(crate) fn get_db_dir_path(&mut self) -> String {
-        match env::var("XRD_MOD_FOLDER") {
-            Ok(env_val) => {
-                // println!("XRD_MOD_FOLDER env is set to: {}. Overwriting executable location.",env_val);
-                env_val
-            }
-            _ => {
-                match env::current_exe() {
-                    Ok(exe_path) => {
-                        // println!("Path of this executable is: {}",exe_path.display());
-                        exe_path.parent().unwrap().to_str().unwrap().to_string()
-                    }
-                    Err(e) => {
-                        println!("failed to get current exe path: {e}");
-                        exit(1);
-                    }
-                }
-            }
-        }
-    }
-    pub(crate) fn get_db_file_path(&mut self) -> String {
-        format!("{}/{}", self.get_db_dir_path(), "db.json")
+        // Default profile
+        self.active_profile = DEFAULT_PROFILE_NAME.to_string();
+        self.sync_active_profile();
     }
 
-    pub(crate) fn get_xrd_game_folder(&mut self) -> String {
+    pub(crate) fn get_xrd_game_folder(&mut self) -> XrdResult<String> {
         if self.xrd_game_folder.is_empty() {
-            let mut file_path: String=String::new();
-
-            if cfg!(windows) {
-                let mut steampath=String::new();
-
-                // TODO improve
-
-                #[cfg(target_os = "windows")]
-                {
-                    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-                    let cur_ver = hklm.open_subkey("SOFTWARE\\Wow6432Node\\Valve\\Steam").unwrap();
-                    steampath = cur_ver.get_value("InstallPath").unwrap();
-                }
-
-                file_path = format!("{steampath}\\config\\libraryfolders.vdf").to_string();
-            }
-            else if cfg!(unix) {
-                let home_path = dirs::home_dir().unwrap().to_str().unwrap().to_string();
-                file_path = format!("{home_path}/.steam/root/config/libraryfolders.vdf").to_string();
+            self.xrd_game_folder = find_xrd_game_folder()?;
+        }
+        validate_xrd_game_folder(&self.xrd_game_folder)?;
+        Ok(self.xrd_game_folder.to_string())
+    }
 
-            }
-            else {
-                println!("Neither Linux or Windows detected, skipping tag.");
-                exit(1);
-            }
-            self.xrd_game_folder = get_xrd_folder_from_file(file_path.to_string()).unwrap().to_string();
+    // An empty folder resets it to be detected automatically.
+    pub(crate) fn set_xrd_game_folder(&mut self, xrd_game_folder: String) -> XrdResult<()> {
+        if !xrd_game_folder.is_empty() {
+            validate_xrd_game_folder(&xrd_game_folder)?;
         }
-        self.xrd_game_folder.to_string()
+        self.xrd_game_folder = xrd_game_folder;
+        Ok(())
     }
 }
 
@@ -0,0 +1,102 @@
+// Colors of the TUI, set with "theme" in settings.json.
+// Either the name of a built-in theme (themes/*.json) or the path to a theme file,
+// its missing colors are taken from the dark theme.
+// Colors are names ("red", "lightcyan"), "#rrggbb" or 256 color indexes ("42").
+// NO_COLOR (https://no-color.org) disables the colors whatever the theme.
+
+use std::env;
+use std::fs;
+use std::sync::RwLock;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::error::{XrdError, XrdResult};
+
+pub const DEFAULT_THEME_NAME: &str = "dark";
+
+const BUILT_IN_THEMES: [(&str, &str); 4] = [
+    ("dark", include_str!("../../themes/dark.json")),
+    ("light", include_str!("../../themes/light.json")),
+    ("high-contrast", include_str!("../../themes/high-contrast.json")),
+    ("colorblind-safe", include_str!("../../themes/colorblind-safe.json")),
+];
+
+// Set on every reload of the config, see set_theme.
+static CURRENT_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy)]
+pub struct Theme {
+    // Behind everything, "reset" keeps the one of the terminal.
+    pub(crate) background: Color,
+    pub(crate) text: Color,
+    pub(crate) muted: Color,
+    pub(crate) border: Color,
+    pub(crate) success: Color,
+    pub(crate) warning: Color,
+    pub(crate) error: Color,
+    // List rows alternate between both.
+    pub(crate) row_bg: Color,
+    pub(crate) alt_row_bg: Color,
+    // Each tab uses its own colors if not set.
+    pub(crate) tab_fg: Color,
+    pub(crate) tab_bg: Option<Color>,
+    pub(crate) tab_highlight_bg: Option<Color>,
+    // Release notes
+    pub(crate) heading: Color,
+    pub(crate) link: Color,
+    pub(crate) quote: Color,
+    pub(crate) code: Color,
+    pub(crate) code_bg: Color,
+    pub(crate) marker: Color,
+}
+
+impl Theme {
+    pub(crate) fn load(theme_name: &str) -> XrdResult<Theme> {
+        if is_no_color_set() {
+            return Ok(Theme::no_color());
+        }
+        let theme_name = if theme_name.is_empty() { DEFAULT_THEME_NAME } else { theme_name };
+        let theme_json = match BUILT_IN_THEMES.iter().find(|(name, _)| *name == theme_name) {
+            Some((_, theme_json)) => theme_json.to_string(),
+            None if theme_name.ends_with(".json") => fs::read_to_string(theme_name)?,
+            None => return Err(XrdError::ThemeNotFound(theme_name.to_string())),
+        };
+
+        let mut theme_value = get_dark_theme_value();
+        if let (Value::Object(theme_colors), Value::Object(colors)) = (&mut theme_value, serde_json::from_str(&theme_json)?) {
+            theme_colors.extend(colors);
+        }
+        Ok(serde_json::from_value(theme_value)?)
+    }
+
+    pub(crate) fn dark() -> Theme {
+        serde_json::from_value(get_dark_theme_value()).unwrap_or_default()
+    }
+
+    // Everything in the colors of the terminal, the state is told by the text and symbols.
+    fn no_color() -> Theme {
+        Theme { tab_bg: Some(Color::Reset), tab_highlight_bg: Some(Color::Reset), ..Theme::default() }
+    }
+}
+
+fn get_dark_theme_value() -> Value {
+    serde_json::from_str(BUILT_IN_THEMES[0].1).unwrap_or_default()
+}
+
+pub fn is_no_color_set() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty())
+}
+
+pub fn get_built_in_theme_names() -> Vec<&'static str> {
+    BUILT_IN_THEMES.iter().map(|(name, _)| *name).collect()
+}
+
+pub fn set_theme(theme: Theme) {
+    if let Ok(mut current_theme) = CURRENT_THEME.write() {
+        *current_theme = Some(theme);
+    }
+}
+
+pub fn get_theme() -> Theme {
+    CURRENT_THEME.read().ok().and_then(|current_theme| *current_theme).unwrap_or_else(Theme::dark)
+}
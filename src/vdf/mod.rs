@@ -0,0 +1,303 @@
+// Valve KeyValues (VDF) parser.
+// Used to read Steam's libraryfolders.vdf and appmanifest_*.acf files.
+//
+// "libraryfolders"
+// {
+//     "0"
+//     {
+//         "path"      "C:\\Program Files (x86)\\Steam"
+//         "apps"
+//         {
+//             "520440"        "1234"
+//         }
+//     }
+// }
+
+use crate::error::{XrdError, XrdResult};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfValue {
+    String(String),
+    // Keys are kept in file order, and might be repeated.
+    Object(Vec<(String, VdfValue)>),
+}
+
+impl VdfValue {
+    // Keys are case-insensitive in KeyValues files.
+    pub(crate) fn get(&self, key: &str) -> Option<&VdfValue> {
+        match self {
+            VdfValue::Object(entries) => entries.iter()
+                .find(|(entry_key, _)| entry_key.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value),
+            VdfValue::String(_) => None,
+        }
+    }
+
+    pub(crate) fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|value| value.as_str())
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::String(value) => Some(value),
+            VdfValue::Object(_) => None,
+        }
+    }
+
+    pub(crate) fn entries(&self) -> &[(String, VdfValue)] {
+        match self {
+            VdfValue::Object(entries) => entries,
+            VdfValue::String(_) => &[],
+        }
+    }
+
+    pub(crate) fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    String(String),
+    // Conditionals such as [$WIN32], ignored.
+    Condition(String),
+    OpenBrace,
+    CloseBrace,
+}
+
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(contents: &'a str) -> Self {
+        Self { chars: contents.chars().peekable(), line: 1 }
+    }
+
+    fn error(&self, message: &str) -> XrdError {
+        XrdError::Other(format!("Error parsing VDF file (line {}): {message}", self.line))
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(&c) = self.chars.peek() {
+            if c == '\n' {
+                self.line += 1;
+                self.chars.next();
+            } else if c.is_whitespace() {
+                self.chars.next();
+            } else if c == '/' {
+                // Comments start with "//" and end with the line
+                let mut lookahead = self.chars.clone();
+                lookahead.next();
+                if lookahead.peek() != Some(&'/') {
+                    return;
+                }
+                while let Some(&c) = self.chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.chars.next();
+                }
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> XrdResult<Option<Token>> {
+        self.skip_whitespace_and_comments();
+
+        let c = match self.chars.next() {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        match c {
+            '{' => Ok(Some(Token::OpenBrace)),
+            '}' => Ok(Some(Token::CloseBrace)),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match self.chars.next() {
+                        None => return Err(self.error("unterminated string")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            match self.chars.next() {
+                                Some('n') => value.push('\n'),
+                                Some('t') => value.push('\t'),
+                                Some('\\') => value.push('\\'),
+                                Some('"') => value.push('"'),
+                                // Unknown escapes are kept as they are, backslash included.
+                                Some(other) => { value.push('\\'); value.push(other); }
+                                None => return Err(self.error("unterminated string")),
+                            }
+                        }
+                        Some(other) => {
+                            if other == '\n' {
+                                self.line += 1;
+                            }
+                            value.push(other)
+                        }
+                    }
+                }
+                Ok(Some(Token::String(value)))
+            }
+            '[' => {
+                let mut condition = String::new();
+                loop {
+                    match self.chars.next() {
+                        None => return Err(self.error("unterminated conditional")),
+                        Some(']') => break,
+                        Some(other) => condition.push(other),
+                    }
+                }
+                Ok(Some(Token::Condition(condition)))
+            }
+            _ => {
+                // Unquoted token, ends on whitespace or any special character
+                let mut value = String::from(c);
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                        break;
+                    }
+                    value.push(c);
+                    self.chars.next();
+                }
+                Ok(Some(Token::String(value)))
+            }
+        }
+    }
+}
+
+pub fn parse_vdf(contents: &str) -> XrdResult<VdfValue> {
+    let mut tokenizer = Tokenizer::new(contents);
+    let root = parse_object(&mut tokenizer, true)?;
+    Ok(root)
+}
+
+fn parse_object(tokenizer: &mut Tokenizer, is_root: bool) -> XrdResult<VdfValue> {
+    let mut entries: Vec<(String, VdfValue)> = vec![];
+
+    loop {
+        let key = match tokenizer.next_token()? {
+            None if is_root => break,
+            None => return Err(tokenizer.error("missing closing brace")),
+            Some(Token::CloseBrace) if !is_root => break,
+            Some(Token::CloseBrace) => return Err(tokenizer.error("unexpected closing brace")),
+            Some(Token::OpenBrace) => return Err(tokenizer.error("expected a key, found an opening brace")),
+            Some(Token::Condition(_)) => continue,
+            Some(Token::String(key)) => key,
+        };
+
+        let value = match tokenizer.next_token()? {
+            Some(Token::OpenBrace) => parse_object(tokenizer, false)?,
+            Some(Token::String(value)) => VdfValue::String(value),
+            Some(Token::Condition(_)) | Some(Token::CloseBrace) | None => {
+                return Err(tokenizer.error(&format!("missing value for the key '{key}'")))
+            }
+        };
+
+        entries.push((key, value));
+    }
+
+    Ok(VdfValue::Object(entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(value: &str) -> VdfValue {
+        VdfValue::String(value.to_string())
+    }
+
+    #[test]
+    fn parses_nested_objects() {
+        let root = parse_vdf(r#"
+            "libraryfolders"
+            {
+                "0"
+                {
+                    "path"      "C:\\Program Files (x86)\\Steam"
+                    "apps"
+                    {
+                        "520440"        "1234"
+                    }
+                }
+                "1"
+                {
+                    "path"      "/mnt/games/SteamLibrary"
+                }
+            }
+        "#).unwrap();
+
+        let library_folders = root.get("libraryfolders").unwrap();
+        assert_eq!(library_folders.entries().len(), 2);
+        let first_library = library_folders.get("0").unwrap();
+        assert_eq!(first_library.get_str("path"), Some(r"C:\Program Files (x86)\Steam"));
+        assert_eq!(first_library.get("apps").unwrap().get_str("520440"), Some("1234"));
+        assert_eq!(library_folders.get("1").unwrap().get_str("path"), Some("/mnt/games/SteamLibrary"));
+        assert!(!library_folders.get("1").unwrap().contains_key("apps"));
+    }
+
+    #[test]
+    fn parses_escapes() {
+        let root = parse_vdf(r#""key" "tab\tnew\nline \"quoted\" back\\slash \q""#).unwrap();
+        assert_eq!(root.get_str("key"), Some("tab\tnew\nline \"quoted\" back\\slash \\q"));
+    }
+
+    #[test]
+    fn keys_are_case_insensitive_and_keep_their_order() {
+        let root = parse_vdf(r#""AppState" { "Name" "Guilty Gear Xrd" "name" "repeated" }"#).unwrap();
+        let app_state = root.get("appstate").unwrap();
+        assert_eq!(app_state.get_str("NAME"), Some("Guilty Gear Xrd"));
+        assert_eq!(app_state.entries(), &[("Name".to_string(), string("Guilty Gear Xrd")), ("name".to_string(), string("repeated"))]);
+    }
+
+    #[test]
+    fn skips_comments_and_conditionals() {
+        let root = parse_vdf(r#"
+            // Comment
+            "root" // Trailing comment
+            {
+                "windows_only" "1" [$WIN32]
+                unquoted value
+                "url" "https://example.com/path"
+            }
+        "#).unwrap();
+        let root = root.get("root").unwrap();
+        assert_eq!(root.get_str("windows_only"), Some("1"));
+        assert_eq!(root.get_str("unquoted"), Some("value"));
+        assert_eq!(root.get_str("url"), Some("https://example.com/path"));
+    }
+
+    #[test]
+    fn empty_file_is_an_empty_object() {
+        assert_eq!(parse_vdf("").unwrap(), VdfValue::Object(vec![]));
+        assert_eq!(parse_vdf("// Only a comment\n").unwrap(), VdfValue::Object(vec![]));
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let malformed_files = [
+            r#""key" "unterminated"#,
+            r#""key" "value\"#,
+            r#""root" { "key" "value""#,
+            r#""key" "value" }"#,
+            r#"{ "key" "value" }"#,
+            r#""root" { "key" }"#,
+            r#""key""#,
+            r#""key" [$WIN32"#,
+        ];
+        for contents in malformed_files {
+            assert!(parse_vdf(contents).is_err(), "'{contents}' should fail to parse");
+        }
+    }
+
+    #[test]
+    fn errors_tell_the_line() {
+        let error = parse_vdf("\"root\"\n{\n\"key\"\n}").unwrap_err();
+        assert!(error.to_string().contains("line 4"), "{error}");
+    }
+}
@@ -0,0 +1,189 @@
+// Comparison of release tags, so a downgrade on GitHub or a pinned older tag isn't taken as an update.
+// Handles semantic versions ("v1.2.3", "1.2.3-beta.1"), plain integers ("1") and dates ("2025-03-12").
+// When the tags can't be compared, or are the same version, the publication dates decide.
+
+use std::cmp::Ordering;
+use chrono::{DateTime, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionComparison {
+    Newer,
+    Older,
+    Same,
+}
+
+impl From<Ordering> for VersionComparison {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => VersionComparison::Newer,
+            Ordering::Less => VersionComparison::Older,
+            Ordering::Equal => VersionComparison::Same,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ParsedVersion {
+    Date(NaiveDate),
+    // Numeric components ("1.2.3", "1"), and the pre-release identifiers ("beta.1").
+    Numeric(Vec<u64>, Vec<String>),
+}
+
+impl ParsedVersion {
+    fn parse(tag_name: &str) -> Option<ParsedVersion> {
+        // "v1.2", "release-1.2", "Release 2025-03-12"...
+        let version = &tag_name[tag_name.find(|c: char| c.is_ascii_digit())?..];
+
+        for date_format in ["%Y-%m-%d", "%Y_%m_%d"] {
+            if let Ok(date) = NaiveDate::parse_from_str(version, date_format) {
+                return Some(ParsedVersion::Date(date));
+            }
+        }
+
+        // Build metadata ("+build.5") doesn't take part in the comparison.
+        let version = version.split('+').next().unwrap_or_default();
+        let (core, prerelease) = match version.split_once('-') {
+            Some((core, prerelease)) => (core, prerelease.split('.').map(|identifier| identifier.to_string()).collect()),
+            None => (version, vec![]),
+        };
+        let components: Vec<u64> = core.split('.').map(|component| component.parse().ok()).collect::<Option<_>>()?;
+        Some(ParsedVersion::Numeric(components, prerelease))
+    }
+
+    // None if the versions use different schemes.
+    fn compare(&self, other: &ParsedVersion) -> Option<Ordering> {
+        match (self, other) {
+            (ParsedVersion::Date(date), ParsedVersion::Date(other_date)) => Some(date.cmp(other_date)),
+            (ParsedVersion::Numeric(components, prerelease), ParsedVersion::Numeric(other_components, other_prerelease)) => {
+                // "1.2" is the same as "1.2.0"
+                let length = components.len().max(other_components.len());
+                let get_component = |components: &Vec<u64>, index: usize| components.get(index).copied().unwrap_or(0);
+                let components_ordering = (0..length)
+                    .map(|index| get_component(components, index).cmp(&get_component(other_components, index)))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal);
+                Some(components_ordering.then_with(|| compare_prereleases(prerelease, other_prerelease)))
+            }
+            _ => None,
+        }
+    }
+}
+
+// Semver precedence: a release is newer than its pre-releases, numeric identifiers are compared as numbers.
+fn compare_prereleases(prerelease: &[String], other_prerelease: &[String]) -> Ordering {
+    match (prerelease.is_empty(), other_prerelease.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+    for (identifier, other_identifier) in prerelease.iter().zip(other_prerelease) {
+        let ordering = match (identifier.parse::<u64>(), other_identifier.parse::<u64>()) {
+            (Ok(number), Ok(other_number)) => number.cmp(&other_number),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => identifier.cmp(other_identifier),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    prerelease.len().cmp(&other_prerelease.len())
+}
+
+fn compare_published_at(published_at: &str, other_published_at: &str) -> Option<Ordering> {
+    let published_at = DateTime::parse_from_rfc3339(published_at).ok()?;
+    let other_published_at = DateTime::parse_from_rfc3339(other_published_at).ok()?;
+    Some(published_at.cmp(&other_published_at))
+}
+
+// How the release (tag_name, published_at) compares against the installed one.
+pub fn compare_versions(installed_tag_name: &str, installed_published_at: &str, tag_name: &str, published_at: &str) -> VersionComparison {
+    let tag_ordering = match (ParsedVersion::parse(installed_tag_name), ParsedVersion::parse(tag_name)) {
+        (Some(installed_version), Some(version)) => version.compare(&installed_version),
+        _ if installed_tag_name == tag_name => Some(Ordering::Equal),
+        _ => None,
+    };
+    if let Some(ordering) = tag_ordering.filter(|ordering| ordering.is_ne()) {
+        return ordering.into();
+    }
+
+    // Same version published again, or tags that can't be compared.
+    match compare_published_at(published_at, installed_published_at) {
+        Some(ordering) => ordering.into(),
+        None if tag_ordering.is_some() => VersionComparison::Same,
+        // Nothing to compare with (e.g. not installed yet)
+        None => VersionComparison::Newer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EARLIER: &str = "2025-01-01T00:00:00Z";
+    const LATER: &str = "2025-06-01T00:00:00Z";
+
+    fn compare_tags(installed_tag_name: &str, tag_name: &str) -> VersionComparison {
+        compare_versions(installed_tag_name, EARLIER, tag_name, EARLIER)
+    }
+
+    #[test]
+    fn compares_semantic_versions() {
+        assert_eq!(compare_tags("v1.2.3", "v1.2.4"), VersionComparison::Newer);
+        assert_eq!(compare_tags("v1.10.0", "v1.9.0"), VersionComparison::Older);
+        assert_eq!(compare_tags("1.2", "v1.2.0"), VersionComparison::Same);
+        assert_eq!(compare_tags("release-1.2", "Release 1.3"), VersionComparison::Newer);
+        assert_eq!(compare_tags("1", "2"), VersionComparison::Newer);
+        assert_eq!(compare_tags("v1.2.3+build.1", "v1.2.3+build.2"), VersionComparison::Same);
+    }
+
+    #[test]
+    fn releases_are_newer_than_their_prereleases() {
+        assert_eq!(compare_tags("v1.0.0-rc.1", "v1.0.0"), VersionComparison::Newer);
+        assert_eq!(compare_tags("v1.0.0", "v1.0.0-rc.1"), VersionComparison::Older);
+        assert_eq!(compare_tags("v0.9.0", "v1.0.0-beta"), VersionComparison::Newer);
+    }
+
+    #[test]
+    fn follows_the_prerelease_precedence() {
+        // 1.0.0-alpha < 1.0.0-alpha.1 < 1.0.0-alpha.beta < 1.0.0-beta < 1.0.0-beta.2 < 1.0.0-beta.11 < 1.0.0-rc.1
+        let tag_names = ["1.0.0-alpha", "1.0.0-alpha.1", "1.0.0-alpha.beta", "1.0.0-beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0"];
+        for pair in tag_names.windows(2) {
+            assert_eq!(compare_tags(pair[0], pair[1]), VersionComparison::Newer, "{} -> {}", pair[0], pair[1]);
+            assert_eq!(compare_tags(pair[1], pair[0]), VersionComparison::Older, "{} -> {}", pair[1], pair[0]);
+        }
+    }
+
+    #[test]
+    fn compares_date_tags() {
+        assert_eq!(compare_tags("2025-03-12", "2025-04-01"), VersionComparison::Newer);
+        assert_eq!(compare_tags("2025_04_01", "2025-03-12"), VersionComparison::Older);
+        assert_eq!(compare_tags("Release 2025-03-12", "2025_03_12"), VersionComparison::Same);
+    }
+
+    #[test]
+    fn mixed_schemes_fall_back_to_the_publication_date() {
+        assert_eq!(compare_versions("2025-03-12", EARLIER, "v1.0.0", LATER), VersionComparison::Newer);
+        assert_eq!(compare_versions("v2.0.0", LATER, "2025-03-12", EARLIER), VersionComparison::Older);
+        assert_eq!(compare_versions("nightly", LATER, "latest", EARLIER), VersionComparison::Older);
+        // Nothing else to compare with
+        assert_eq!(compare_versions("v1.0.0", "", "2025-03-12", ""), VersionComparison::Newer);
+    }
+
+    #[test]
+    fn same_version_is_decided_by_the_publication_date() {
+        // Deleted and published again under the same tag
+        assert_eq!(compare_versions("v1.0.0", EARLIER, "v1.0.0", LATER), VersionComparison::Newer);
+        assert_eq!(compare_versions("v1.0.0", LATER, "v1.0", EARLIER), VersionComparison::Older);
+        assert_eq!(compare_versions("v1.0.0", EARLIER, "v1.0.0", EARLIER), VersionComparison::Same);
+        assert_eq!(compare_versions("nightly", "", "nightly", ""), VersionComparison::Same);
+    }
+
+    #[test]
+    fn version_takes_precedence_over_the_publication_date() {
+        // An older version published later, such as a backport.
+        assert_eq!(compare_versions("v2.0.0", EARLIER, "v1.9.1", LATER), VersionComparison::Older);
+        assert_eq!(compare_versions("v1.9.1", LATER, "v2.0.0", EARLIER), VersionComparison::Newer);
+    }
+}
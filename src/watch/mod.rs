@@ -0,0 +1,216 @@
+// Headless update checks, to leave running in the background or to call from a cron job / scheduled task.
+// Each check writes the result to update_status.json, and sends a desktop notification for the new versions found.
+
+use std::collections::BTreeMap;
+use std::fs::create_dir_all;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use chrono::Utc;
+use notify_rust::Notification;
+use serde::{Deserialize, Serialize};
+use crate::error::{XrdError, XrdResult};
+use crate::functions::{read_optional_file, write_file_atomically};
+use crate::manager::Manager;
+use crate::storage::{get_storage_paths, APP_DIR_NAME};
+
+pub const DEFAULT_WATCH_INTERVAL_MINUTES: u64 = 60;
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct AppUpdateStatus {
+    pub(crate) installed_tag_name: String,
+    pub(crate) latest_tag_name: String,
+    pub(crate) url_source_version: String,
+    pub(crate) new_version: bool,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+impl AppUpdateStatus {
+    // A failed lookup keeps the result of the previous check, otherwise the new version
+    // found before would be reported again on the next successful check.
+    fn keep_previous_result(&mut self, previous_app_status: Option<&AppUpdateStatus>) {
+        if let Some(previous_app_status) = previous_app_status {
+            self.latest_tag_name = previous_app_status.latest_tag_name.to_string();
+            self.url_source_version = previous_app_status.url_source_version.to_string();
+            self.new_version = previous_app_status.new_version;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct UpdateStatus {
+    // RFC 3339
+    pub(crate) checked_at: String,
+    pub(crate) apps: BTreeMap<String, AppUpdateStatus>,
+}
+
+impl UpdateStatus {
+    // New versions not reported by the previous check, so each one is only notified once.
+    fn get_unreported_new_versions(&self, previous_status: &Option<UpdateStatus>) -> Vec<(String, AppUpdateStatus)> {
+        self.apps.iter()
+            .filter(|(_, app_status)| app_status.new_version)
+            .filter(|(app_name, app_status)| {
+                let previous_app_status = previous_status.as_ref().and_then(|previous_status| previous_status.apps.get(*app_name));
+                !previous_app_status.is_some_and(|previous_app_status| previous_app_status.new_version && previous_app_status.latest_tag_name == app_status.latest_tag_name)
+            })
+            .map(|(app_name, app_status)| (app_name.to_string(), app_status.clone()))
+            .collect()
+    }
+}
+
+// Latest tag check over the enabled apps.
+pub fn check_updates(manager: &Manager, previous_status: &Option<UpdateStatus>) -> UpdateStatus {
+    let mut update_status = UpdateStatus {
+        checked_at: Utc::now().to_rfc3339(),
+        apps: BTreeMap::new(),
+    };
+
+    for app_name in manager.get_enabled_app_names() {
+        let app = &manager.config.apps[&app_name];
+        let mut app_status = AppUpdateStatus {
+            installed_tag_name: app.tag_name.to_string(),
+            ..AppUpdateStatus::default()
+        };
+        match manager.get_latest_release(&app_name) {
+            Ok(latest_release) => {
+                app_status.new_version = app.has_new_version(&latest_release.tag_info);
+                app_status.latest_tag_name = latest_release.tag_info.tag_name.to_string();
+                app_status.url_source_version = latest_release.tag_info.html_url.to_string();
+            }
+            Err(e) => {
+                app_status.keep_previous_result(previous_status.as_ref().and_then(|previous_status| previous_status.apps.get(&app_name)));
+                app_status.error = Some(e.to_string());
+            }
+        }
+        update_status.apps.insert(app_name, app_status);
+    }
+    update_status
+}
+
+fn read_update_status(update_status_file_path: &String) -> XrdResult<Option<UpdateStatus>> {
+    match read_optional_file(update_status_file_path)? {
+        // An unreadable status file is just replaced.
+        Some(contents) => Ok(serde_json::from_str(&contents).ok()),
+        None => Ok(None),
+    }
+}
+
+fn send_update_notification(new_versions: &[(String, AppUpdateStatus)]) -> XrdResult<()> {
+    let body: Vec<String> = new_versions.iter()
+        .map(|(app_name, app_status)| format!("{app_name}: '{}' -> '{}'", app_status.installed_tag_name, app_status.latest_tag_name))
+        .collect();
+
+    Notification::new()
+        .appname(APP_DIR_NAME)
+        .summary("New Guilty Gear Xrd mod versions")
+        .body(&body.join("\n"))
+        .show()
+        .map_err(|e| XrdError::Other(format!("Couldn't send the desktop notification: {e}")))?;
+    Ok(())
+}
+
+// Runs a single check, returning the new versions found that weren't reported before.
+pub fn run_update_check(send_notification: bool) -> XrdResult<Vec<(String, AppUpdateStatus)>> {
+    // Loaded on every check, to pick up the changes made from the TUI.
+    let mut manager = Manager::default();
+    manager.load_config()?;
+    if manager.config.offline_mode {
+        println!("Offline mode: only the cached releases are checked.");
+    }
+
+    let update_status_file_path = get_storage_paths()?.get_update_status_file_path();
+    let previous_status = read_update_status(&update_status_file_path)?;
+    let update_status = check_updates(&manager, &previous_status);
+    if let Some(update_status_dir) = Path::new(&update_status_file_path).parent() {
+        create_dir_all(update_status_dir)?;
+    }
+    write_file_atomically(&update_status_file_path, serde_json::to_string_pretty(&update_status)?.as_bytes())?;
+
+    let new_versions = update_status.get_unreported_new_versions(&previous_status);
+    if send_notification && !new_versions.is_empty() {
+        // The status file is still useful without a notification daemon.
+        if let Err(e) = send_update_notification(&new_versions) {
+            println!("[⚠️ ] {e}");
+        }
+    }
+
+    for (app_name, app_status) in &update_status.apps {
+        match (&app_status.error, app_status.new_version) {
+            (Some(e), _) => println!("[⚠️ ] Error checking '{app_name}': {e}"),
+            (None, true) => println!("[⚠️ ] '{app_name}' has a new version: '{}' -> '{}'", app_status.installed_tag_name, app_status.latest_tag_name),
+            (None, false) => {}
+        }
+    }
+    Ok(new_versions)
+}
+
+pub fn watch(interval: Duration, send_notification: bool) -> XrdResult<()> {
+    loop {
+        // A failed check (e.g. the config being saved at the same time) is retried on the next one.
+        match run_update_check(send_notification) {
+            Ok(new_versions) => println!("[{}] Checked for updates, {} new versions since the last check.", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), new_versions.len()),
+            Err(e) => println!("[{}] Error checking for updates: {e}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")),
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_status(latest_tag_name: &str, new_version: bool) -> AppUpdateStatus {
+        AppUpdateStatus {
+            installed_tag_name: "v1.0.0".to_string(),
+            latest_tag_name: latest_tag_name.to_string(),
+            new_version,
+            ..AppUpdateStatus::default()
+        }
+    }
+
+    fn update_status(apps: &[(&str, AppUpdateStatus)]) -> UpdateStatus {
+        UpdateStatus {
+            checked_at: String::new(),
+            apps: apps.iter().map(|(app_name, app_status)| (app_name.to_string(), app_status.clone())).collect(),
+        }
+    }
+
+    fn get_unreported_app_names(update_status: &UpdateStatus, previous_status: &Option<UpdateStatus>) -> Vec<String> {
+        update_status.get_unreported_new_versions(previous_status).into_iter().map(|(app_name, _)| app_name).collect()
+    }
+
+    #[test]
+    fn reports_every_new_version_without_a_previous_status() {
+        let status = update_status(&[("a/new", app_status("v1.1.0", true)), ("b/up_to_date", app_status("v1.0.0", false))]);
+        assert_eq!(get_unreported_app_names(&status, &None), vec!["a/new"]);
+    }
+
+    #[test]
+    fn skips_the_versions_already_reported() {
+        let previous_status = update_status(&[("a/new", app_status("v1.1.0", true))]);
+        let status = update_status(&[("a/new", app_status("v1.1.0", true))]);
+        assert!(get_unreported_app_names(&status, &Some(previous_status)).is_empty());
+    }
+
+    #[test]
+    fn reports_a_newer_tag_than_the_one_reported() {
+        let previous_status = update_status(&[("a/new", app_status("v1.1.0", true))]);
+        let status = update_status(&[("a/new", app_status("v1.2.0", true))]);
+        assert_eq!(get_unreported_app_names(&status, &Some(previous_status)), vec!["a/new"]);
+    }
+
+    #[test]
+    fn failed_check_doesnt_report_the_same_version_again() {
+        let first_status = update_status(&[("a/new", app_status("v1.1.0", true))]);
+
+        let mut failed_app_status = app_status("", false);
+        failed_app_status.keep_previous_result(first_status.apps.get("a/new"));
+        failed_app_status.error = Some("network error".to_string());
+        let failed_status = update_status(&[("a/new", failed_app_status)]);
+        assert!(get_unreported_app_names(&failed_status, &Some(first_status)).is_empty());
+
+        let status = update_status(&[("a/new", app_status("v1.1.0", true))]);
+        assert!(get_unreported_app_names(&status, &Some(failed_status)).is_empty());
+    }
+}